@@ -4,9 +4,14 @@
 //! enabling intelligent plant care recommendations and troubleshooting.
 
 use crate::error::DatabaseError;
-use crate::types::{Species, CultivationRecord};
+use crate::types::{Species, CultivationRecord, CultivationEvent};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use std::future::Future;
+use std::time::Duration;
+use sqlx::SqlitePool;
+use crate::queries::context_documents;
 
 #[cfg(feature = "contextlite")]
 use contextlite_client::ContextLiteClient;
@@ -15,8 +20,126 @@ use contextlite_client::ContextLiteClient;
 #[derive(Debug, Clone)]
 pub struct BotanicalContext {
     #[cfg(feature = "contextlite")]
+    #[allow(dead_code)] // Used once the ContextLite API calls replace the mocks
     client: ContextLiteClient,
     workspace_id: String,
+    confidence_model: ConfidenceModel,
+    request_timeout: Duration,
+    context_template: ContextTemplate,
+}
+
+/// Default upper bound on a single ContextLite request
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Layout used for recommendation context unless overridden
+pub const DEFAULT_CONTEXT_TEMPLATE: &str = concat!(
+    "Species: {species}\n",
+    "Authority: {authority}\n",
+    "Genus: {genus}\n",
+    "Current stage: {stage}\n",
+    "Notes: {notes}\n",
+    "Recent events: {events}",
+);
+
+/// Most recent cultivation events rendered into `{events}`
+pub const RECENT_EVENT_LIMIT: usize = 5;
+
+/// Layout of the context assembled for a recommendation request
+///
+/// Placeholders are `{species}` (specific epithet), `{authority}`, `{genus}`
+/// (genus ID), `{stage}` and `{notes}` (from the latest cultivation record),
+/// `{events}` (the latest `RECENT_EVENT_LIMIT` cultivation events, oldest
+/// first, separated by `; `) and `{query}`. A line is dropped when every placeholder on it renders empty, so
+/// optional details such as notes disappear cleanly. Unknown placeholders are
+/// left as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextTemplate {
+    template: String,
+}
+
+impl ContextTemplate {
+    /// Create a template from text containing placeholders
+    pub fn new(template: impl Into<String>) -> Self {
+        Self { template: template.into() }
+    }
+
+    /// Fill in the template for a species, its cultivation records and a query
+    pub fn render(&self, species: &Species, records: &[CultivationRecord], query: &str) -> String {
+        self.render_with_events(species, records, &[], query)
+    }
+
+    /// Fill in the template, including recent cultivation events
+    ///
+    /// `events` should be in time order, as returned by
+    /// `queries::cultivation::get_recent_cultivation_events`.
+    pub fn render_with_events(
+        &self,
+        species: &Species,
+        records: &[CultivationRecord],
+        events: &[CultivationEvent],
+        query: &str,
+    ) -> String {
+        let latest = records.last();
+        let recent_events = &events[events.len().saturating_sub(RECENT_EVENT_LIMIT)..];
+        let lookup = |name: &str| -> Option<String> {
+            match name {
+                "species" => Some(species.specific_epithet.clone()),
+                "authority" => Some(species.authority.clone()),
+                "genus" => Some(species.genus_id.to_string()),
+                "stage" => Some(latest.map(|r| format!("{:?}", r.growth_stage)).unwrap_or_default()),
+                "notes" => Some(latest.and_then(|r| r.notes.clone()).unwrap_or_default()),
+                "events" => Some(recent_events.iter().map(describe_event).collect::<Vec<_>>().join("; ")),
+                "query" => Some(query.to_string()),
+                _ => None,
+            }
+        };
+
+        self.template
+            .lines()
+            .filter_map(|line| {
+                let mut rendered = String::new();
+                let mut placeholders = 0;
+                let mut filled = 0;
+                let mut rest = line;
+                while let Some(open) = rest.find('{') {
+                    rendered.push_str(&rest[..open]);
+                    let after = &rest[open + 1..];
+                    match after.find('}').and_then(|close| lookup(&after[..close]).map(|value| (close, value))) {
+                        Some((close, value)) => {
+                            placeholders += 1;
+                            if !value.is_empty() {
+                                filled += 1;
+                            }
+                            rendered.push_str(&value);
+                            rest = &after[close + 1..];
+                        }
+                        None => {
+                            rendered.push('{');
+                            rest = after;
+                        }
+                    }
+                }
+                rendered.push_str(rest);
+                (placeholders == 0 || filled > 0).then_some(rendered)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// One event as rendered into `{events}`, e.g. `2024-05-01 watering (500 ml)`
+fn describe_event(event: &CultivationEvent) -> String {
+    let mut text = format!("{} {}", event.timestamp.format("%Y-%m-%d"), event.event_type);
+    if let Some(detail) = event.detail.as_deref().filter(|d| !d.trim().is_empty()) {
+        text.push_str(&format!(" ({})", detail));
+    }
+    text
+}
+
+impl Default for ContextTemplate {
+    fn default() -> Self {
+        Self::new(DEFAULT_CONTEXT_TEMPLATE)
+    }
 }
 
 /// Plant context query parameters
@@ -49,6 +172,58 @@ pub struct ContextDocument {
     pub source: String,
     pub relevance_score: f32,
     pub content_snippet: String,
+    /// When the document was indexed, used by `ConfidenceModel::WeightedByRecency`
+    #[serde(default)]
+    pub indexed_at: Option<DateTime<Utc>>,
+}
+
+/// Heuristic for deriving a response's confidence score from its documents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ConfidenceModel {
+    /// Mean relevance score across all documents
+    #[default]
+    MeanRelevance,
+    /// Relevance score of the single best document
+    MaxRelevance,
+    /// Mean relevance weighted by document age, halving every `RECENCY_HALF_LIFE_DAYS`.
+    /// Undated documents are weighted as one half-life old.
+    WeightedByRecency,
+}
+
+/// Age in days at which a document counts half as much under `WeightedByRecency`
+pub const RECENCY_HALF_LIFE_DAYS: f32 = 30.0;
+
+impl ConfidenceModel {
+    /// Compute a confidence score in `0.0..=1.0` from document relevance scores.
+    ///
+    /// Returns `0.0` when there are no documents to back the response.
+    pub fn score(&self, documents: &[ContextDocument]) -> f32 {
+        if documents.is_empty() {
+            return 0.0;
+        }
+
+        let score = match self {
+            ConfidenceModel::MeanRelevance => {
+                documents.iter().map(|d| d.relevance_score).sum::<f32>() / documents.len() as f32
+            }
+            ConfidenceModel::MaxRelevance => {
+                documents.iter().map(|d| d.relevance_score).fold(f32::MIN, f32::max)
+            }
+            ConfidenceModel::WeightedByRecency => {
+                let now = Utc::now();
+                let (weighted_sum, total_weight) = documents.iter().fold((0.0, 0.0), |(sum, total), d| {
+                    let age_days = d.indexed_at
+                        .map(|t| (now - t).num_seconds().max(0) as f32 / 86_400.0)
+                        .unwrap_or(RECENCY_HALF_LIFE_DAYS);
+                    let weight = 0.5f32.powf(age_days / RECENCY_HALF_LIFE_DAYS);
+                    (sum + d.relevance_score * weight, total + weight)
+                });
+                if total_weight > 0.0 { weighted_sum / total_weight } else { 0.0 }
+            }
+        };
+
+        score.clamp(0.0, 1.0)
+    }
 }
 
 impl BotanicalContext {
@@ -56,11 +231,14 @@ impl BotanicalContext {
     #[cfg(feature = "contextlite")]
     pub fn new(base_url: &str, _auth_token: &str, workspace_id: &str) -> Result<Self, DatabaseError> {
         let client = ContextLiteClient::new(base_url)
-            .map_err(|e| DatabaseError::ContextLiteError(e.to_string()))?;
+            .map_err(|e| DatabaseError::contextlite_source("Failed to create ContextLite client", e))?;
         
         Ok(Self {
             client,
             workspace_id: workspace_id.to_string(),
+            confidence_model: ConfidenceModel::default(),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            context_template: ContextTemplate::default(),
         })
     }
 
@@ -69,50 +247,104 @@ impl BotanicalContext {
     pub fn new(_base_url: &str, _auth_token: &str, workspace_id: &str) -> Result<Self, DatabaseError> {
         Ok(Self {
             workspace_id: workspace_id.to_string(),
+            confidence_model: ConfidenceModel::default(),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            context_template: ContextTemplate::default(),
         })
     }
 
+    /// Use a different heuristic for response confidence scores
+    pub fn with_confidence_model(mut self, model: ConfidenceModel) -> Self {
+        self.confidence_model = model;
+        self
+    }
+
+    /// The heuristic used for response confidence scores
+    pub fn confidence_model(&self) -> ConfidenceModel {
+        self.confidence_model
+    }
+
+    /// Override the per-request timeout (defaults to `DEFAULT_REQUEST_TIMEOUT`)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// The per-request timeout
+    pub fn timeout(&self) -> Duration {
+        self.request_timeout
+    }
+
+    /// Use a different layout for recommendation context
+    pub fn with_context_template(mut self, template: ContextTemplate) -> Self {
+        self.context_template = template;
+        self
+    }
+
+    /// The layout used for recommendation context
+    pub fn context_template(&self) -> &ContextTemplate {
+        &self.context_template
+    }
+
+    /// Run a ContextLite request, failing with a retryable `NetworkError` if it
+    /// does not finish within the configured timeout
+    #[cfg_attr(not(feature = "contextlite"), allow(dead_code))]
+    async fn with_request_timeout<T, F>(&self, operation: &str, request: F) -> Result<T, DatabaseError>
+    where
+        F: Future<Output = Result<T, DatabaseError>>,
+    {
+        tokio::time::timeout(self.request_timeout, request)
+            .await
+            .map_err(|elapsed| DatabaseError::network_source(
+                format!("ContextLite {} timed out after {:?}", operation, self.request_timeout),
+                elapsed,
+            ))?
+    }
+
     /// Get AI-powered plant care recommendations
-    #[cfg(feature = "contextlite")]
     pub async fn get_plant_recommendations(
         &self,
         species: &Species,
         cultivation_records: &[CultivationRecord],
         query: &str,
     ) -> Result<PlantContextResponse, DatabaseError> {
-        // Build context from plant data
-        let mut context_parts = vec![
-            format!("Species: {}", species.specific_epithet),
-            format!("Authority: {}", species.authority),
-            format!("Genus: {}", species.genus_id),
-        ];
+        self.get_plant_recommendations_with_events(species, cultivation_records, &[], query).await
+    }
 
-        if !cultivation_records.is_empty() {
-            let latest_record = &cultivation_records[cultivation_records.len() - 1];
-            context_parts.push(format!("Current stage: {:?}", latest_record.growth_stage));
-            if let Some(notes) = &latest_record.notes {
-                context_parts.push(format!("Notes: {}", notes));
-            }
-        }
+    /// Get AI-powered plant care recommendations, with recent cultivation events in the context
+    #[cfg(feature = "contextlite")]
+    pub async fn get_plant_recommendations_with_events(
+        &self,
+        species: &Species,
+        cultivation_records: &[CultivationRecord],
+        events: &[CultivationEvent],
+        query: &str,
+    ) -> Result<PlantContextResponse, DatabaseError> {
+        self.with_request_timeout("recommendation request", async {
+            // Build context from plant data
+            let context = self.context_template.render_with_events(species, cultivation_records, events, query);
 
-        // TODO: Implement actual ContextLite API call once we discover correct method
-        // For now, provide mock response
-        Ok(PlantContextResponse {
-            plant_id: species.id, // Using species ID as plant ID for now
-            query: query.to_string(),
-            context: format!("Context for {} ({})", species.specific_epithet, query),
-            recommendations: vec!["Mock recommendation".to_string()],
-            relevant_documents: vec![],
-            confidence_score: 0.8,
-        })
+            // TODO: Implement actual ContextLite API call once we discover correct method
+            // For now, provide mock response
+            let relevant_documents = vec![];
+            Ok(PlantContextResponse {
+                plant_id: species.id, // Using species ID as plant ID for now
+                query: query.to_string(),
+                context,
+                recommendations: vec!["Mock recommendation".to_string()],
+                confidence_score: self.confidence_model.score(&relevant_documents),
+                relevant_documents,
+            })
+        }).await
     }
 
     /// Get AI-powered plant care recommendations (mock without contextlite feature)
     #[cfg(not(feature = "contextlite"))]
-    pub async fn get_plant_recommendations(
+    pub async fn get_plant_recommendations_with_events(
         &self,
         species: &Species,
         _cultivation_records: &[CultivationRecord],
+        _events: &[CultivationEvent],
         query: &str,
     ) -> Result<PlantContextResponse, DatabaseError> {
         // Mock response when ContextLite is not available
@@ -129,8 +361,10 @@ impl BotanicalContext {
     /// Query general botanical knowledge
     #[cfg(feature = "contextlite")]
     pub async fn query_botanical_knowledge(&self, query: &str) -> Result<String, DatabaseError> {
-        // TODO: Implement actual ContextLite API call
-        Ok(format!("Mock botanical knowledge for: {}", query))
+        self.with_request_timeout("knowledge query", async {
+            // TODO: Implement actual ContextLite API call
+            Ok(format!("Mock botanical knowledge for: {}", query))
+        }).await
     }
 
     /// Query general botanical knowledge (mock without contextlite feature)
@@ -146,19 +380,16 @@ impl BotanicalContext {
         species: &Species,
         records: &[CultivationRecord],
     ) -> Result<(), DatabaseError> {
-        // Assemble plant data into ContextLite document
-        let _plant_data = format!(
-            "SPECIES: {} ({})\nRECORDS: {}",
-            species.specific_epithet,
-            species.authority,
-            records.len()
-        );
+        self.with_request_timeout("indexing request", async {
+            // Assemble plant data into ContextLite document
+            let _preview = preview_plant_document(species, records);
 
-        // TODO: Implement actual ContextLite document indexing
-        // For now, just log the data being indexed
-        log::info!("Would index {} records for species {}", records.len(), species.specific_epithet);
+            // TODO: Implement actual ContextLite document indexing
+            // For now, just log the data being indexed
+            log::info!("Would index {} records for species {}", records.len(), species.specific_epithet);
 
-        Ok(())
+            Ok(())
+        }).await
     }
 
     /// Add plant data to ContextLite knowledge base (no-op without contextlite feature)
@@ -170,36 +401,246 @@ impl BotanicalContext {
     ) -> Result<(), DatabaseError> {
         Ok(()) // No-op when ContextLite is not available
     }
+
+    /// Index a species and record which document it was indexed under
+    ///
+    /// Returns the document ID. The mapping lets `reindex_species` update the
+    /// same document and `purge_deleted_species` remove it once the species
+    /// is deleted.
+    pub async fn index_species(
+        &self,
+        pool: &SqlitePool,
+        species: &Species,
+        records: &[CultivationRecord],
+    ) -> Result<String, DatabaseError> {
+        self.index_plant_data(species, records).await?;
+
+        let document_id = preview_plant_document(species, records).document_id;
+        context_documents::record_context_document(pool, species.id, &self.workspace_id, &document_id).await?;
+        Ok(document_id)
+    }
+
+    /// Update the document a species was previously indexed under
+    ///
+    /// Falls back to `index_species` when the species has no recorded document
+    /// in this workspace.
+    pub async fn reindex_species(
+        &self,
+        pool: &SqlitePool,
+        species: &Species,
+        records: &[CultivationRecord],
+    ) -> Result<String, DatabaseError> {
+        let existing = context_documents::get_context_document(pool, species.id, &self.workspace_id).await?;
+        let Some(existing) = existing.filter(|doc| !doc.pending_removal) else {
+            return self.index_species(pool, species, records).await;
+        };
+
+        self.update_plant_document(&existing.document_id, species, records).await?;
+        context_documents::record_context_document(pool, species.id, &self.workspace_id, &existing.document_id).await?;
+        Ok(existing.document_id)
+    }
+
+    /// Remove the documents of deleted species from this workspace
+    ///
+    /// Deleting a species only marks its document for removal; call this
+    /// afterwards to remove it. Returns the number of documents removed.
+    pub async fn purge_deleted_species(&self, pool: &SqlitePool) -> Result<usize, DatabaseError> {
+        let pending = context_documents::get_documents_pending_removal(pool, &self.workspace_id).await?;
+        for document in &pending {
+            self.remove_plant_document(&document.document_id).await?;
+            context_documents::forget_context_document(pool, document.species_id, &self.workspace_id).await?;
+        }
+        Ok(pending.len())
+    }
+
+    /// Replace the content of an existing ContextLite document
+    #[cfg(feature = "contextlite")]
+    async fn update_plant_document(
+        &self,
+        document_id: &str,
+        species: &Species,
+        records: &[CultivationRecord],
+    ) -> Result<(), DatabaseError> {
+        self.with_request_timeout("update request", async {
+            let _preview = preview_plant_document(species, records);
+
+            // TODO: Implement actual ContextLite document update
+            log::info!("Would update document {} with {} records", document_id, records.len());
+
+            Ok(())
+        }).await
+    }
+
+    /// Replace the content of an existing ContextLite document (no-op without contextlite feature)
+    #[cfg(not(feature = "contextlite"))]
+    async fn update_plant_document(
+        &self,
+        _document_id: &str,
+        _species: &Species,
+        _records: &[CultivationRecord],
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    /// Remove a document from the ContextLite knowledge base
+    #[cfg(feature = "contextlite")]
+    async fn remove_plant_document(&self, document_id: &str) -> Result<(), DatabaseError> {
+        self.with_request_timeout("removal request", async {
+            // TODO: Implement actual ContextLite document removal
+            log::info!("Would remove document {} from workspace {}", document_id, self.workspace_id);
+
+            Ok(())
+        }).await
+    }
+
+    /// Remove a document from the ContextLite knowledge base (no-op without contextlite feature)
+    #[cfg(not(feature = "contextlite"))]
+    async fn remove_plant_document(&self, _document_id: &str) -> Result<(), DatabaseError> {
+        Ok(())
+    }
 }
 
-/// Extract recommendations from context text
-fn extract_recommendations(context: &str) -> Vec<String> {
-    let mut recommendations = Vec::new();
-    
-    // Look for recommendation patterns in text
-    if context.contains("nutrient") && context.contains("deficiency") {
-        recommendations.push("Consider adjusting nutrient levels".to_string());
+/// Preview of the document `index_plant_data` would send to ContextLite
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlantDocumentPreview {
+    /// Document ID the species would be indexed under
+    pub document_id: String,
+    /// Assembled document text
+    pub content: String,
+    /// Rough token estimate (about four characters per token)
+    pub estimated_tokens: usize,
+}
+
+/// Assemble the ContextLite document for a species without indexing it
+///
+/// Purely local, so it can be used to estimate indexing cost or inspect
+/// documents whether or not the `contextlite` feature is enabled.
+pub fn preview_plant_document(species: &Species, records: &[CultivationRecord]) -> PlantDocumentPreview {
+    let mut lines = vec![format!("SPECIES: {} ({})", species.specific_epithet, species.authority)];
+    if let Some(year) = species.publication_year {
+        lines.push(format!("PUBLISHED: {}", year));
     }
-    
-    if context.contains("water") && (context.contains("over") || context.contains("under")) {
-        recommendations.push("Review watering schedule".to_string());
+    if let Some(status) = &species.conservation_status {
+        lines.push(format!("CONSERVATION STATUS: {}", status));
     }
-    
-    if context.contains("light") && context.contains("stress") {
-        recommendations.push("Adjust lighting conditions".to_string());
+    lines.push(format!("RECORDS: {}", records.len()));
+    for record in records {
+        let mut line = format!("- {} {:?} by {}", record.recorded_at.format("%Y-%m-%d"), record.growth_stage, record.cultivator);
+        if let Some(notes) = &record.notes {
+            line.push_str(&format!(": {}", notes));
+        }
+        lines.push(line);
     }
-    
-    if context.contains("pH") {
-        recommendations.push("Check and adjust soil/water pH levels".to_string());
+
+    let content = lines.join("\n");
+    PlantDocumentPreview {
+        document_id: format!("species-{}", species.id),
+        estimated_tokens: content.chars().count().div_ceil(4),
+        content,
     }
-    
-    if context.contains("harvest") && context.contains("ready") {
-        recommendations.push("Consider harvest timing evaluation".to_string());
+}
+
+/// Language of the text passed to `extract_recommendations_in` and of the advice it returns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    German,
+}
+
+/// A keyword rule: fires when every group has at least one of its words in the text
+struct RecommendationRule {
+    groups: &'static [&'static [&'static str]],
+    advice: &'static str,
+    /// Match the words exactly as written rather than against the lowercased text
+    case_sensitive: bool,
+}
+
+impl RecommendationRule {
+    const fn new(groups: &'static [&'static [&'static str]], advice: &'static str) -> Self {
+        Self { groups, advice, case_sensitive: false }
     }
+
+    /// `pH` is written the same in every language and must keep its case,
+    /// so that words like "phosphorus" do not trigger it
+    const fn ph(advice: &'static str) -> Self {
+        Self { groups: &[&["pH"]], advice, case_sensitive: true }
+    }
+
+    fn matches(&self, context: &str, lowercased: &str) -> bool {
+        let text = if self.case_sensitive { context } else { lowercased };
+        self.groups.iter().all(|words| words.iter().any(|word| text.contains(word)))
+    }
+}
+
+/// Advice given when no rule matches
+fn fallback_advice(language: Language) -> &'static str {
+    match language {
+        Language::English => "Review cultivation data and environmental conditions",
+        Language::Spanish => "Revise los datos de cultivo y las condiciones ambientales",
+        Language::German => "Anbaudaten und Umgebungsbedingungen überprüfen",
+    }
+}
+
+/// Keyword rules per language, in the order their advice is returned
+///
+/// Keywords are lowercase and matched against the lowercased text, except for
+/// case-sensitive rules.
+fn recommendation_rules(language: Language) -> &'static [RecommendationRule] {
+    match language {
+        Language::English => ENGLISH_RULES,
+        Language::Spanish => SPANISH_RULES,
+        Language::German => GERMAN_RULES,
+    }
+}
+
+const ENGLISH_RULES: &[RecommendationRule] = &[
+    RecommendationRule::new(&[&["nutrient"], &["deficiency"]], "Consider adjusting nutrient levels"),
+    RecommendationRule::new(&[&["water"], &["over", "under"]], "Review watering schedule"),
+    RecommendationRule::new(&[&["light"], &["stress"]], "Adjust lighting conditions"),
+    RecommendationRule::ph("Check and adjust soil/water pH levels"),
+    RecommendationRule::new(&[&["harvest"], &["ready"]], "Consider harvest timing evaluation"),
+];
+
+const SPANISH_RULES: &[RecommendationRule] = &[
+    RecommendationRule::new(&[&["nutriente"], &["deficiencia", "carencia"]], "Considere ajustar los niveles de nutrientes"),
+    RecommendationRule::new(&[&["agua", "riego"], &["exceso", "falta", "demasiad", "poca", "poco"]], "Revise el calendario de riego"),
+    RecommendationRule::new(&[&["luz"], &["estrés", "estres"]], "Ajuste las condiciones de iluminación"),
+    RecommendationRule::ph("Compruebe y ajuste el pH del suelo o del agua"),
+    RecommendationRule::new(&[&["cosecha"], &["lista", "listo", "madura", "maduro"]], "Considere evaluar el momento de la cosecha"),
+];
+
+const GERMAN_RULES: &[RecommendationRule] = &[
+    RecommendationRule::new(&[&["nährstoff"], &["mangel"]], "Nährstoffversorgung anpassen"),
+    RecommendationRule::new(&[&["wasser", "gieß", "bewässer"], &["über", "unter", "zu viel", "zu wenig"]], "Gießplan überprüfen"),
+    RecommendationRule::new(&[&["licht"], &["stress"]], "Lichtverhältnisse anpassen"),
+    RecommendationRule::ph("pH-Wert von Boden und Wasser prüfen und anpassen"),
+    RecommendationRule::new(&[&["ernte"], &["bereit", "reif"]], "Erntezeitpunkt prüfen"),
+];
+
+/// Extract recommendations from English context text
+pub fn extract_recommendations(context: &str) -> Vec<String> {
+    extract_recommendations_in(context, Language::English)
+}
+
+/// Extract recommendations from context text in the given language
+///
+/// Keywords match case-insensitively, except `pH`, which must be written as
+/// such so that words like "phosphorus" do not trigger it. Advice is returned
+/// in the same language; when nothing matches, a single general
+/// recommendation is returned.
+pub fn extract_recommendations_in(context: &str, language: Language) -> Vec<String> {
+    let lowercased = context.to_lowercase();
+    let mut recommendations: Vec<String> = recommendation_rules(language)
+        .iter()
+        .filter(|rule| rule.matches(context, &lowercased))
+        .map(|rule| rule.advice.to_string())
+        .collect();
     
     // If no specific patterns found, provide general recommendation
     if recommendations.is_empty() {
-        recommendations.push("Review cultivation data and environmental conditions".to_string());
+        recommendations.push(fallback_advice(language).to_string());
     }
     
     recommendations
@@ -257,11 +698,194 @@ mod tests {
 
     #[test]
     fn test_recommendation_extraction() {
-        // TODO: Test recommendation extraction once ContextLite API is working
-        // For now, test basic text pattern matching
-        let test_context = "The plant shows signs of nutrient deficiency and may need water adjustment";
+        let context = "The plant shows signs of nutrient deficiency, light stress and a pH drift";
+        assert_eq!(extract_recommendations(context), vec![
+            "Consider adjusting nutrient levels".to_string(),
+            "Adjust lighting conditions".to_string(),
+            "Check and adjust soil/water pH levels".to_string(),
+        ]);
+        assert_eq!(extract_recommendations("phosphorus looks fine"), vec![
+            "Review cultivation data and environmental conditions".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_localized_recommendation_extraction() {
+        let spanish = extract_recommendations_in("Hojas amarillas por deficiencia de nutrientes y exceso de riego", Language::Spanish);
+        assert_eq!(spanish, vec![
+            "Considere ajustar los niveles de nutrientes".to_string(),
+            "Revise el calendario de riego".to_string(),
+        ]);
+        
+        let german = extract_recommendations_in("Deutlicher Nährstoffmangel, Ernte bald reif", Language::German);
+        assert_eq!(german, vec!["Nährstoffversorgung anpassen".to_string(), "Erntezeitpunkt prüfen".to_string()]);
         
-        assert!(test_context.contains("nutrient"));
-        assert!(test_context.contains("water"));
+        // English keywords do not fire for another language
+        let fallback = extract_recommendations_in("nutrient deficiency", Language::German);
+        assert_eq!(fallback, vec!["Anbaudaten und Umgebungsbedingungen überprüfen".to_string()]);
+    }
+
+    fn document(relevance_score: f32, indexed_at: Option<DateTime<Utc>>) -> ContextDocument {
+        ContextDocument {
+            id: Uuid::new_v4().to_string(),
+            title: "Test document".to_string(),
+            source: "test".to_string(),
+            relevance_score,
+            content_snippet: String::new(),
+            indexed_at,
+        }
+    }
+
+    #[test]
+    fn test_confidence_models() {
+        let docs = vec![document(0.9, None), document(0.5, None), document(0.1, None)];
+
+        assert!((ConfidenceModel::MeanRelevance.score(&docs) - 0.5).abs() < 1e-6);
+        assert!((ConfidenceModel::MaxRelevance.score(&docs) - 0.9).abs() < 1e-6);
+        assert_eq!(ConfidenceModel::default(), ConfidenceModel::MeanRelevance);
+
+        for model in [ConfidenceModel::MeanRelevance, ConfidenceModel::MaxRelevance, ConfidenceModel::WeightedByRecency] {
+            assert_eq!(model.score(&[]), 0.0, "{:?} should score no documents as 0", model);
+        }
+    }
+
+    #[test]
+    fn test_weighted_by_recency_favors_recent_documents() {
+        let now = Utc::now();
+        let docs = vec![
+            document(0.9, Some(now)),
+            document(0.1, Some(now - chrono::Duration::days(365))),
+        ];
+
+        let weighted = ConfidenceModel::WeightedByRecency.score(&docs);
+        let mean = ConfidenceModel::MeanRelevance.score(&docs);
+        assert!(weighted > mean, "Recent high-relevance document should dominate: {} vs {}", weighted, mean);
+        assert!(weighted <= 0.9);
+    }
+
+    #[test]
+    fn test_preview_plant_document() {
+        let species = Species::new(Uuid::new_v4(), "rubiginosa".to_string(), "L.".to_string(), Some(1753), Some("LC".to_string()));
+        let mut record = CultivationRecord::new(species.id, GrowthStage::Flowering, "grower".to_string());
+        record.notes = Some("First blooms".to_string());
+
+        let preview = preview_plant_document(&species, &[record]);
+
+        assert_eq!(preview.document_id, format!("species-{}", species.id));
+        assert!(preview.content.starts_with("SPECIES: rubiginosa (L.)"));
+        assert!(preview.content.contains("RECORDS: 1"));
+        assert!(preview.content.contains("Flowering by grower: First blooms"));
+        assert_eq!(preview.estimated_tokens, preview.content.chars().count().div_ceil(4));
+    }
+
+    #[test]
+    fn test_default_and_custom_timeout() {
+        let context = BotanicalContext::new("http://localhost:8090", "test-token", "test-workspace")
+            .expect("Failed to create context");
+        assert_eq!(context.timeout(), DEFAULT_REQUEST_TIMEOUT);
+
+        let context = context.with_timeout(std::time::Duration::from_secs(60));
+        assert_eq!(context.timeout(), std::time::Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_slow_request_times_out_as_network_error() {
+        let context = BotanicalContext::new("http://localhost:8090", "test-token", "test-workspace")
+            .expect("Failed to create context")
+            .with_timeout(std::time::Duration::from_millis(20));
+
+        let slow_request = async {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            Ok(())
+        };
+        let result = context.with_request_timeout("test request", slow_request).await;
+
+        match result {
+            Err(e @ DatabaseError::NetworkError { .. }) => {
+                assert!(e.is_retryable());
+                assert!(std::error::Error::source(&e).is_some(), "Timeout should be the error source");
+            }
+            other => panic!("Expected a network timeout error, got {:?}", other),
+        }
+
+        let fast = context.with_request_timeout("test request", async { Ok(42) }).await;
+        assert_eq!(fast.expect("Fast request should complete"), 42);
+    }
+
+    #[test]
+    fn test_with_confidence_model() {
+        let context = BotanicalContext::new("http://localhost:8090", "test-token", "test-workspace")
+            .expect("Failed to create context")
+            .with_confidence_model(ConfidenceModel::MaxRelevance);
+
+        assert_eq!(context.confidence_model(), ConfidenceModel::MaxRelevance);
+    }
+
+    #[test]
+    fn test_default_context_template_matches_previous_layout() {
+        let species = Species::new(Uuid::new_v4(), "sativa".to_string(), "L.".to_string(), None, None);
+        let template = ContextTemplate::default();
+
+        assert_eq!(
+            template.render(&species, &[], "yellow leaves"),
+            format!("Species: sativa\nAuthority: L.\nGenus: {}", species.genus_id),
+            "Stage and notes lines should be dropped without records"
+        );
+
+        let mut record = CultivationRecord::new(species.id, GrowthStage::Flowering, "grower".to_string());
+        record.notes = Some("Leaf tips curling".to_string());
+        let rendered = template.render(&species, &[record], "yellow leaves");
+        assert!(rendered.ends_with("Current stage: Flowering\nNotes: Leaf tips curling"));
+    }
+
+    #[test]
+    fn test_context_template_renders_recent_events() {
+        use crate::types::{CultivationEvent, EventType};
+        use chrono::TimeZone;
+
+        let species = Species::new(Uuid::new_v4(), "sativa".to_string(), "L.".to_string(), None, None);
+        let events: Vec<CultivationEvent> = (1..=6)
+            .map(|day| {
+                let timestamp = Utc.with_ymd_and_hms(2024, 5, day, 8, 0, 0).unwrap();
+                let detail = (day == 6).then(|| "500 ml".to_string());
+                CultivationEvent::new(species.id, EventType::Watering, timestamp, detail)
+            })
+            .collect();
+
+        let rendered = ContextTemplate::default().render_with_events(&species, &[], &events, "droop");
+        let line = rendered.lines().last().unwrap();
+        assert!(line.starts_with("Recent events: 2024-05-02 watering; "), "Only the latest events belong: {}", line);
+        assert!(line.ends_with("2024-05-06 watering (500 ml)"));
+        assert_eq!(line.matches("watering").count(), RECENT_EVENT_LIMIT);
+    }
+
+    #[test]
+    fn test_custom_context_template() {
+        let species = Species::new(Uuid::new_v4(), "sativa".to_string(), "L.".to_string(), None, None);
+        let template = ContextTemplate::new("Q: {query}\n{species} ({authority}) at {stage}\nNotes: {notes}\nKeep {unknown} and {braces");
+
+        assert_eq!(
+            template.render(&species, &[], "{notes}?"),
+            "Q: {notes}?\nsativa (L.) at \nKeep {unknown} and {braces",
+            "Substituted values must not be re-expanded"
+        );
+
+        let context = BotanicalContext::new("http://localhost:8090", "test-token", "test-workspace")
+            .expect("Failed to create context")
+            .with_context_template(template.clone());
+        assert_eq!(context.context_template(), &template);
+    }
+
+    #[cfg(feature = "contextlite")]
+    #[tokio::test]
+    async fn test_recommendations_use_context_template() {
+        let species = Species::new(Uuid::new_v4(), "sativa".to_string(), "L.".to_string(), None, None);
+        let context = BotanicalContext::new("http://localhost:8090", "test-token", "test-workspace")
+            .expect("Failed to create context")
+            .with_context_template(ContextTemplate::new("{species}: {query}"));
+
+        let response = context.get_plant_recommendations(&species, &[], "when to harvest").await
+            .expect("Recommendation request failed");
+        assert_eq!(response.context, "sativa: when to harvest");
     }
 }
\ No newline at end of file
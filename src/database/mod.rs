@@ -1,6 +1,16 @@
-use sqlx::SqlitePool;
+use std::str::FromStr;
+use std::time::Duration;
+use sqlx::{Arguments, ConnectOptions, Executor, Sqlite, SqlitePool, Statement};
+use sqlx::sqlite::{SqliteArguments, SqliteConnectOptions, SqlitePoolOptions, SqliteRow};
+use uuid::Uuid;
 use crate::error::DatabaseError;
 
+/// Default threshold above which statements are logged as slow
+pub const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// Default time a connection waits on a locked database before failing
+pub const DEFAULT_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Configuration for the botanical database connection
 #[derive(Debug, Clone)]
 pub struct DatabaseConfig {
@@ -12,6 +22,13 @@ pub struct DatabaseConfig {
     
     /// Enable foreign key constraints
     pub foreign_keys: bool,
+    
+    /// Statements taking longer than this are logged at warn level with their
+    /// SQL and elapsed time; `None` disables slow-statement logging entirely
+    pub slow_query_threshold: Option<Duration>,
+    
+    /// How long a connection waits for another writer's lock before failing
+    pub busy_timeout: Duration,
 }
 
 impl Default for DatabaseConfig {
@@ -20,6 +37,8 @@ impl Default for DatabaseConfig {
             url: "sqlite:botanical.db".to_string(),
             max_connections: 10,
             foreign_keys: true,
+            slow_query_threshold: Some(DEFAULT_SLOW_QUERY_THRESHOLD),
+            busy_timeout: DEFAULT_BUSY_TIMEOUT,
         }
     }
 }
@@ -31,30 +50,171 @@ impl DatabaseConfig {
             url: "sqlite::memory:".to_string(),
             max_connections: 1,
             foreign_keys: true,
+            slow_query_threshold: Some(DEFAULT_SLOW_QUERY_THRESHOLD),
+            busy_timeout: DEFAULT_BUSY_TIMEOUT,
         }
     }
     
+    /// Set the slow-statement logging threshold, or `None` to disable it
+    pub fn with_slow_query_threshold(mut self, threshold: Option<Duration>) -> Self {
+        self.slow_query_threshold = threshold;
+        self
+    }
+    
+    /// Set how long connections wait on a locked database
+    pub fn with_busy_timeout(mut self, timeout: Duration) -> Self {
+        self.busy_timeout = timeout;
+        self
+    }
+    
+    /// Build a configuration from environment variables
+    ///
+    /// `DATABASE_URL` is required. `BOTANICA_MAX_CONNECTIONS` and
+    /// `BOTANICA_BUSY_TIMEOUT_MS` are optional and fall back to the defaults.
+    /// A missing URL or a malformed number is a `ConfigError` naming the variable.
+    pub fn from_env() -> Result<Self, DatabaseError> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+    
+    /// Build a configuration from any variable source, see `from_env`
+    pub(crate) fn from_lookup<F>(lookup: F) -> Result<Self, DatabaseError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let url = lookup("DATABASE_URL")
+            .filter(|url| !url.trim().is_empty())
+            .ok_or_else(|| DatabaseError::config("DATABASE_URL is not set"))?;
+        let mut config = Self { url, ..Self::default() };
+        
+        if let Some(value) = lookup("BOTANICA_MAX_CONNECTIONS") {
+            config.max_connections = match value.trim().parse::<u32>() {
+                Ok(n) if n > 0 => n,
+                _ => return Err(DatabaseError::config(format!(
+                    "BOTANICA_MAX_CONNECTIONS must be a positive integer, got '{}'", value
+                ))),
+            };
+        }
+        
+        if let Some(value) = lookup("BOTANICA_BUSY_TIMEOUT_MS") {
+            let millis: u64 = value.trim().parse().map_err(|_| DatabaseError::config(format!(
+                "BOTANICA_BUSY_TIMEOUT_MS must be a whole number of milliseconds, got '{}'", value
+            )))?;
+            config.busy_timeout = Duration::from_millis(millis);
+        }
+        
+        Ok(config)
+    }
+    
     /// Create a new database configuration for file-based database
     pub fn file<S: AsRef<str>>(path: S) -> Self {
         Self {
             url: format!("sqlite:{}", path.as_ref()),
             max_connections: 10,
             foreign_keys: true,
+            slow_query_threshold: Some(DEFAULT_SLOW_QUERY_THRESHOLD),
+            busy_timeout: DEFAULT_BUSY_TIMEOUT,
         }
     }
 }
 
+/// A value bound to a `?` placeholder by `BotanicalDatabase::query_scalar`
+/// and `BotanicalDatabase::query_as`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue {
+    /// SQL NULL
+    Null,
+    
+    /// 64-bit integer; booleans bind as 0 or 1
+    Integer(i64),
+    
+    /// Floating point number
+    Real(f64),
+    
+    /// Text, including UUIDs in their hyphenated form
+    Text(String),
+    
+    /// Raw bytes
+    Blob(Vec<u8>),
+}
+
+impl From<i64> for SqlValue {
+    fn from(value: i64) -> Self {
+        SqlValue::Integer(value)
+    }
+}
+
+impl From<i32> for SqlValue {
+    fn from(value: i32) -> Self {
+        SqlValue::Integer(value.into())
+    }
+}
+
+impl From<bool> for SqlValue {
+    fn from(value: bool) -> Self {
+        SqlValue::Integer(value.into())
+    }
+}
+
+impl From<f64> for SqlValue {
+    fn from(value: f64) -> Self {
+        SqlValue::Real(value)
+    }
+}
+
+impl From<&str> for SqlValue {
+    fn from(value: &str) -> Self {
+        SqlValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for SqlValue {
+    fn from(value: String) -> Self {
+        SqlValue::Text(value)
+    }
+}
+
+/// UUIDs bind as text, matching how the crate stores IDs
+impl From<Uuid> for SqlValue {
+    fn from(value: Uuid) -> Self {
+        SqlValue::Text(value.to_string())
+    }
+}
+
+impl<T: Into<SqlValue>> From<Option<T>> for SqlValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(SqlValue::Null, Into::into)
+    }
+}
+
 /// Main database connection pool for botanical operations
+///
+/// Cloning is cheap: `SqlitePool` is reference-counted, so every clone shares
+/// the same pool and its connections, and closing one closes them all. Hand a
+/// clone to each task or web handler rather than wrapping the database in an
+/// `Arc`. Query functions take the `&SqlitePool` from `pool()`, so they work
+/// with any clone.
 #[derive(Debug, Clone)]
 pub struct BotanicalDatabase {
     /// SQLite connection pool
     pub pool: SqlitePool,
+    
+    /// Optional read-only pool for queries, see `with_read_pool`
+    read_pool: Option<SqlitePool>,
 }
 
 impl BotanicalDatabase {
     /// Create a new database connection from configuration
     pub async fn new(config: DatabaseConfig) -> Result<Self, DatabaseError> {
-        let pool = SqlitePool::connect(&config.url).await?;
+        let mut options = SqliteConnectOptions::from_str(&config.url)?
+            .busy_timeout(config.busy_timeout);
+        options = match config.slow_query_threshold {
+            Some(threshold) => options.log_slow_statements(log::LevelFilter::Warn, threshold),
+            None => options.log_slow_statements(log::LevelFilter::Off, Duration::ZERO),
+        };
+        let pool = SqlitePoolOptions::new()
+            .max_connections(config.max_connections)
+            .connect_with(options)
+            .await?;
         
         // Enable foreign key constraints if requested
         if config.foreign_keys {
@@ -63,7 +223,37 @@ impl BotanicalDatabase {
                 .await?;
         }
         
-        Ok(Self { pool })
+        Ok(Self { pool, read_pool: None })
+    }
+    
+    /// Add a separate read-only pool for reporting and other read-heavy queries
+    ///
+    /// `url` is usually the primary's own database file, or a copy of it.
+    /// Connections are opened read-only with `PRAGMA query_only`, so any write
+    /// through `read_pool()` fails instead of taking the write lock. With the
+    /// primary in WAL mode, readers never block its writer. The read pool uses
+    /// the primary's connection limit and the default busy timeout. An
+    /// in-memory URL opens a separate, empty database rather than the primary's.
+    pub async fn with_read_pool(mut self, url: &str) -> Result<Self, DatabaseError> {
+        let options = SqliteConnectOptions::from_str(url)?
+            .read_only(true)
+            .pragma("query_only", "ON")
+            .busy_timeout(DEFAULT_BUSY_TIMEOUT);
+        let read_pool = SqlitePoolOptions::new()
+            .max_connections(self.pool.options().get_max_connections())
+            .connect_with(options)
+            .await?;
+        
+        self.read_pool = Some(read_pool);
+        Ok(self)
+    }
+    
+    /// Create a database configured from environment variables
+    ///
+    /// See `DatabaseConfig::from_env` for the variables read. Like `new`, this
+    /// does not run migrations.
+    pub async fn from_env() -> Result<Self, DatabaseError> {
+        Self::new(DatabaseConfig::from_env()?).await
     }
     
     /// Create a new in-memory database for testing
@@ -84,13 +274,80 @@ impl BotanicalDatabase {
         Ok(())
     }
     
+    /// Run a custom query that returns a single value
+    ///
+    /// An escape hatch for queries the crate does not provide. Values must be
+    /// passed through `binds`, one per `?` placeholder; a mismatched count is a
+    /// `ValidationError` rather than SQLite's silent NULL. The crate does not
+    /// check the SQL itself: callers are responsible for its correctness and for
+    /// keeping it in step with the schema. No rows is a `NotFound` error.
+    pub async fn query_scalar<T>(&self, sql: &str, binds: &[SqlValue]) -> Result<T, DatabaseError>
+    where
+        T: for<'r> sqlx::Decode<'r, Sqlite> + sqlx::Type<Sqlite> + Send + Unpin,
+    {
+        let arguments = self.bind_arguments(sql, binds).await?;
+        sqlx::query_scalar_with(sql, arguments)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| DatabaseError::not_found("Query returned no rows"))
+    }
+    
+    /// Run a custom query and map each row with `FromRow`
+    ///
+    /// Binding and responsibility for the SQL are as for `query_scalar`.
+    pub async fn query_as<T>(&self, sql: &str, binds: &[SqlValue]) -> Result<Vec<T>, DatabaseError>
+    where
+        T: for<'r> sqlx::FromRow<'r, SqliteRow> + Send + Unpin,
+    {
+        let arguments = self.bind_arguments(sql, binds).await?;
+        Ok(sqlx::query_as_with(sql, arguments).fetch_all(&self.pool).await?)
+    }
+    
+    async fn bind_arguments<'q>(&self, sql: &'q str, binds: &[SqlValue]) -> Result<SqliteArguments<'q>, DatabaseError> {
+        let statement = (&self.pool).prepare(sql).await?;
+        let expected = match statement.parameters() {
+            Some(sqlx::Either::Right(count)) => count,
+            Some(sqlx::Either::Left(types)) => types.len(),
+            None => binds.len(),
+        };
+        if expected != binds.len() {
+            return Err(DatabaseError::validation(format!(
+                "Query expects {} bound values, got {}", expected, binds.len()
+            )));
+        }
+        
+        let mut arguments = SqliteArguments::default();
+        for value in binds {
+            let added = match value {
+                SqlValue::Null => arguments.add(None::<String>),
+                SqlValue::Integer(v) => arguments.add(*v),
+                SqlValue::Real(v) => arguments.add(*v),
+                SqlValue::Text(v) => arguments.add(v.clone()),
+                SqlValue::Blob(v) => arguments.add(v.clone()),
+            };
+            added.map_err(|e| DatabaseError::validation(e.to_string()))?;
+        }
+        Ok(arguments)
+    }
+    
     /// Get a reference to the underlying connection pool
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
     
-    /// Close the database connection pool
+    /// Get the pool for read-only queries
+    ///
+    /// The read pool if one was added with `with_read_pool`, otherwise the
+    /// primary pool. Pass it to query functions that do not write.
+    pub fn read_pool(&self) -> &SqlitePool {
+        self.read_pool.as_ref().unwrap_or(&self.pool)
+    }
+    
+    /// Close the database connection pool, and the read pool if there is one
     pub async fn close(&self) {
+        if let Some(read_pool) = &self.read_pool {
+            read_pool.close().await;
+        }
         self.pool.close().await;
     }
 }
\ No newline at end of file
@@ -1,4 +1,8 @@
 use std::fmt;
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Boxed underlying error carried by integration error variants
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
 /// Database error types for botanical operations
 #[derive(Debug)]
@@ -22,7 +26,16 @@ pub enum DatabaseError {
     ConstraintViolation(String),
     
     /// ContextLite integration error
-    ContextLiteError(String),
+    ContextLiteError {
+        message: String,
+        source: Option<BoxError>,
+    },
+    
+    /// Transient network failure, such as a request timeout; safe to retry
+    NetworkError {
+        message: String,
+        source: Option<BoxError>,
+    },
 }
 
 impl fmt::Display for DatabaseError {
@@ -34,7 +47,8 @@ impl fmt::Display for DatabaseError {
             DatabaseError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
             DatabaseError::NotFound(msg) => write!(f, "Not found: {}", msg),
             DatabaseError::ConstraintViolation(msg) => write!(f, "Constraint violation: {}", msg),
-            DatabaseError::ContextLiteError(msg) => write!(f, "ContextLite error: {}", msg),
+            DatabaseError::ContextLiteError { message, .. } => write!(f, "ContextLite error: {}", message),
+            DatabaseError::NetworkError { message, .. } => write!(f, "Network error: {}", message),
         }
     }
 }
@@ -43,11 +57,48 @@ impl std::error::Error for DatabaseError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             DatabaseError::SqlxError(e) => Some(e),
+            DatabaseError::ContextLiteError { source, .. } | DatabaseError::NetworkError { source, .. } => {
+                source.as_deref().map(|e| e as &(dyn std::error::Error + 'static))
+            }
             _ => None,
         }
     }
 }
 
+/// Broad class of an error, for choosing how an API responds to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The request was invalid, referred to something missing or conflicted with stored data
+    Client,
+    
+    /// The database or its setup failed
+    Server,
+    
+    /// An external service such as ContextLite failed
+    External,
+}
+
+/// Client-safe form of a `DatabaseError`, serialized as `{ code, category, message }`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiError {
+    /// Stable machine-readable code, e.g. `validation_error`
+    pub code: String,
+    
+    /// Broad class of the error
+    pub category: ErrorCategory,
+    
+    /// Human-readable description, safe to show to clients
+    pub message: String,
+}
+
+/// Serializes as the client-safe `ApiError`
+impl Serialize for DatabaseError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_api_error().serialize(serializer)
+    }
+}
+
 impl From<sqlx::Error> for DatabaseError {
     fn from(error: sqlx::Error) -> Self {
         DatabaseError::SqlxError(error)
@@ -82,6 +133,74 @@ impl DatabaseError {
     
     /// Create a new ContextLite error
     pub fn contextlite<S: Into<String>>(msg: S) -> Self {
-        DatabaseError::ContextLiteError(msg.into())
+        DatabaseError::ContextLiteError { message: msg.into(), source: None }
+    }
+    
+    /// Create a new ContextLite error caused by an underlying error
+    pub fn contextlite_source<S: Into<String>, E: Into<BoxError>>(msg: S, source: E) -> Self {
+        DatabaseError::ContextLiteError { message: msg.into(), source: Some(source.into()) }
+    }
+    
+    /// Create a new network error
+    pub fn network<S: Into<String>>(msg: S) -> Self {
+        DatabaseError::NetworkError { message: msg.into(), source: None }
+    }
+    
+    /// Create a new network error caused by an underlying error
+    pub fn network_source<S: Into<String>, E: Into<BoxError>>(msg: S, source: E) -> Self {
+        DatabaseError::NetworkError { message: msg.into(), source: Some(source.into()) }
+    }
+    
+    /// Whether the failed operation may succeed if retried
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, DatabaseError::NetworkError { .. })
+    }
+    
+    /// Stable machine-readable code for the error variant
+    pub fn code(&self) -> &'static str {
+        match self {
+            DatabaseError::SqlxError(_) => "database_error",
+            DatabaseError::MigrationError(_) => "migration_error",
+            DatabaseError::ConfigError(_) => "config_error",
+            DatabaseError::ValidationError(_) => "validation_error",
+            DatabaseError::NotFound(_) => "not_found",
+            DatabaseError::ConstraintViolation(_) => "constraint_violation",
+            DatabaseError::ContextLiteError { .. } => "contextlite_error",
+            DatabaseError::NetworkError { .. } => "network_error",
+        }
+    }
+    
+    /// Broad class of the error
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            DatabaseError::ValidationError(_) | DatabaseError::NotFound(_) | DatabaseError::ConstraintViolation(_) => {
+                ErrorCategory::Client
+            }
+            DatabaseError::SqlxError(_) | DatabaseError::MigrationError(_) | DatabaseError::ConfigError(_) => {
+                ErrorCategory::Server
+            }
+            DatabaseError::ContextLiteError { .. } | DatabaseError::NetworkError { .. } => ErrorCategory::External,
+        }
+    }
+    
+    /// Convert to the form returned across an API boundary
+    ///
+    /// SQLx errors can carry SQL text and schema details, so their message is
+    /// replaced with a generic one and the full error is logged at error level
+    /// instead. Other variants keep their `Display` message.
+    pub fn to_api_error(&self) -> ApiError {
+        let message = match self {
+            DatabaseError::SqlxError(e) => {
+                log::error!("Database error returned to client as generic message: {}", e);
+                "An internal database error occurred".to_string()
+            }
+            other => other.to_string(),
+        };
+        
+        ApiError {
+            code: self.code().to_string(),
+            category: self.category(),
+            message,
+        }
     }
 }
\ No newline at end of file
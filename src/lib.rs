@@ -8,12 +8,11 @@ pub mod types;
 pub mod queries;
 pub mod migrations;
 pub mod error;
-
-#[cfg(feature = "contextlite")]
 pub mod contextlite;
+pub mod prelude;
 
 // Re-exports for convenience
-pub use database::{BotanicalDatabase, DatabaseConfig};
+pub use database::{BotanicalDatabase, DatabaseConfig, SqlValue};
 pub use error::DatabaseError;
 pub use types::{Species, Genus, Family};
 
@@ -1,65 +1,462 @@
-use sqlx::{SqlitePool, query};
+use sqlx::{SqlitePool, SqliteConnection, query, query_scalar};
 use crate::error::DatabaseError;
 
 pub mod runner;
 pub mod schemas;
 
 
+/// A single step within a schema migration
+enum Step {
+    /// Execute a SQL statement
+    Sql(&'static str),
+
+    /// Add a column unless it already exists, for databases created before
+    /// migrations were versioned
+    AddColumn {
+        table: &'static str,
+        column: &'static str,
+        definition: &'static str,
+    },
+
+    /// Count writes to a table in `dataset_versions`, through insert, update
+    /// and delete triggers
+    CountWrites {
+        table: &'static str,
+    },
+}
+
+/// A numbered schema migration. Migrations are append-only: never edit one
+/// that has shipped, add a new version instead.
+struct Migration {
+    version: i64,
+    description: &'static str,
+    steps: &'static [Step],
+}
+
+/// Timestamp expression matching the RFC 3339 format sqlx writes for `DateTime<Utc>`
+macro_rules! now_rfc3339 {
+    () => { "strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now')" };
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "taxonomy and specimen tables",
+        steps: &[
+            // Create families table
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS families (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    authority TEXT
+                )
+            "#),
+            // Create genera table
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS genera (
+                    id TEXT PRIMARY KEY,
+                    family_id TEXT NOT NULL,
+                    name TEXT NOT NULL,
+                    authority TEXT,
+                    FOREIGN KEY (family_id) REFERENCES families(id)
+                )
+            "#),
+            // Create species table
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS species (
+                    id TEXT PRIMARY KEY,
+                    genus_id TEXT NOT NULL,
+                    specific_epithet TEXT NOT NULL,
+                    authority TEXT,
+                    publication_year INTEGER,
+                    conservation_status TEXT,
+                    FOREIGN KEY (genus_id) REFERENCES genera(id)
+                )
+            "#),
+            // Create specimens table
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS specimens (
+                    id TEXT PRIMARY KEY,
+                    species_id TEXT NOT NULL,
+                    collector TEXT,
+                    collection_date TEXT,
+                    location TEXT,
+                    notes TEXT,
+                    FOREIGN KEY (species_id) REFERENCES species(id)
+                )
+            "#),
+        ],
+    },
+    Migration {
+        version: 2,
+        description: "unique taxon names for concurrent upserts",
+        steps: &[
+            // Databases from before these indexes may hold exact duplicates, which
+            // would make the indexes fail. Merge each into its earliest row: move
+            // genera to the kept family, then species to the kept genus.
+            Step::Sql(r#"
+                UPDATE genera SET family_id = (
+                    SELECT k.id FROM families k JOIN families f ON k.name = f.name AND k.authority = f.authority
+                    WHERE f.id = genera.family_id ORDER BY k.rowid LIMIT 1
+                )
+                WHERE family_id IN (
+                    SELECT f.id FROM families f WHERE EXISTS (
+                        SELECT 1 FROM families k WHERE k.name = f.name AND k.authority = f.authority AND k.rowid < f.rowid
+                    )
+                )
+            "#),
+            Step::Sql(r#"
+                UPDATE species SET genus_id = (
+                    SELECT k.id FROM genera k JOIN genera g
+                        ON k.family_id = g.family_id AND k.name = g.name AND k.authority = g.authority
+                    WHERE g.id = species.genus_id ORDER BY k.rowid LIMIT 1
+                )
+                WHERE genus_id IN (
+                    SELECT g.id FROM genera g WHERE EXISTS (
+                        SELECT 1 FROM genera k
+                        WHERE k.family_id = g.family_id AND k.name = g.name AND k.authority = g.authority AND k.rowid < g.rowid
+                    )
+                )
+            "#),
+            Step::Sql(r#"
+                DELETE FROM genera WHERE EXISTS (
+                    SELECT 1 FROM genera k
+                    WHERE k.family_id = genera.family_id AND k.name = genera.name AND k.authority = genera.authority
+                      AND k.rowid < genera.rowid
+                )
+            "#),
+            Step::Sql(r#"
+                DELETE FROM families WHERE EXISTS (
+                    SELECT 1 FROM families k
+                    WHERE k.name = families.name AND k.authority = families.authority AND k.rowid < families.rowid
+                )
+            "#),
+            // Families are unique by name and authority
+            Step::Sql("CREATE UNIQUE INDEX IF NOT EXISTS idx_families_name_authority ON families(name, authority)"),
+            // Genera are unique by name and authority within a family
+            Step::Sql("CREATE UNIQUE INDEX IF NOT EXISTS idx_genera_family_name_authority ON genera(family_id, name, authority)"),
+        ],
+    },
+    Migration {
+        version: 3,
+        description: "modification timestamps",
+        steps: &[
+            Step::AddColumn { table: "families", column: "created_at", definition: "TEXT" },
+            Step::AddColumn { table: "families", column: "updated_at", definition: "TEXT" },
+            Step::AddColumn { table: "genera", column: "created_at", definition: "TEXT" },
+            Step::AddColumn { table: "genera", column: "updated_at", definition: "TEXT" },
+            Step::AddColumn { table: "species", column: "created_at", definition: "TEXT" },
+            Step::AddColumn { table: "species", column: "updated_at", definition: "TEXT" },
+            // Stamp pre-existing rows so the next incremental sync picks them up
+            Step::Sql(concat!("UPDATE families SET created_at = ", now_rfc3339!(), " WHERE created_at IS NULL")),
+            Step::Sql(concat!("UPDATE families SET updated_at = ", now_rfc3339!(), " WHERE updated_at IS NULL")),
+            Step::Sql(concat!("UPDATE genera SET created_at = ", now_rfc3339!(), " WHERE created_at IS NULL")),
+            Step::Sql(concat!("UPDATE genera SET updated_at = ", now_rfc3339!(), " WHERE updated_at IS NULL")),
+            Step::Sql(concat!("UPDATE species SET created_at = ", now_rfc3339!(), " WHERE created_at IS NULL")),
+            Step::Sql(concat!("UPDATE species SET updated_at = ", now_rfc3339!(), " WHERE updated_at IS NULL")),
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_species_updated_at ON species(updated_at)"),
+        ],
+    },
+    Migration {
+        version: 4,
+        description: "case-insensitive name prefix indexes",
+        steps: &[
+            // NOCASE indexes let SQLite serve `LIKE 'prefix%'` autocomplete lookups from the index
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_genera_name_nocase ON genera(name COLLATE NOCASE)"),
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_species_epithet_nocase ON species(specific_epithet COLLATE NOCASE)"),
+        ],
+    },
+    Migration {
+        version: 5,
+        description: "species attributes",
+        steps: &[
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS species_attributes (
+                    species_id TEXT NOT NULL,
+                    key TEXT NOT NULL,
+                    value TEXT NOT NULL,
+                    updated_at TEXT,
+                    PRIMARY KEY (species_id, key),
+                    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+                )
+            "#),
+            // Serves find_species_by_attribute lookups
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_species_attributes_key_value ON species_attributes(key, value)"),
+        ],
+    },
+    Migration {
+        version: 6,
+        description: "species media",
+        steps: &[
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS media (
+                    id TEXT PRIMARY KEY,
+                    species_id TEXT NOT NULL,
+                    url TEXT NOT NULL,
+                    media_type TEXT NOT NULL,
+                    license TEXT NOT NULL,
+                    creator TEXT,
+                    title TEXT,
+                    created_at TEXT,
+                    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+                )
+            "#),
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_media_species_id ON media(species_id)"),
+        ],
+    },
+    Migration {
+        version: 7,
+        description: "species name history",
+        steps: &[
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS name_history (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    species_id TEXT NOT NULL,
+                    old_name TEXT NOT NULL,
+                    new_name TEXT NOT NULL,
+                    changed_at TEXT NOT NULL,
+                    reason TEXT,
+                    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+                )
+            "#),
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_name_history_species_id ON name_history(species_id)"),
+        ],
+    },
+    Migration {
+        version: 8,
+        description: "accession number sequences",
+        steps: &[
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS accession_sequences (
+                    year INTEGER PRIMARY KEY,
+                    last_value INTEGER NOT NULL
+                )
+            "#),
+        ],
+    },
+    Migration {
+        version: 9,
+        description: "species provenance",
+        steps: &[
+            Step::AddColumn { table: "species", column: "source", definition: "TEXT NOT NULL DEFAULT 'manual'" },
+            Step::AddColumn { table: "species", column: "source_record_id", definition: "TEXT" },
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_species_source ON species(source, source_record_id)"),
+        ],
+    },
+    Migration {
+        version: 10,
+        description: "species annotations",
+        steps: &[
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS annotations (
+                    id TEXT PRIMARY KEY,
+                    species_id TEXT NOT NULL,
+                    author TEXT NOT NULL,
+                    body TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    resolved INTEGER NOT NULL DEFAULT 0,
+                    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+                )
+            "#),
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_annotations_species_id ON annotations(species_id)"),
+            // Keeps the open-annotation work queue cheap however many are resolved
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_annotations_open ON annotations(created_at) WHERE resolved = 0"),
+        ],
+    },
+    Migration {
+        version: 11,
+        description: "species view statistics",
+        steps: &[
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS species_stats (
+                    species_id TEXT PRIMARY KEY,
+                    view_count INTEGER NOT NULL DEFAULT 0,
+                    last_viewed TEXT NOT NULL,
+                    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+                )
+            "#),
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_species_stats_view_count ON species_stats(view_count)"),
+        ],
+    },
+    Migration {
+        version: 12,
+        description: "cultivation events",
+        steps: &[
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS cultivation_events (
+                    id TEXT PRIMARY KEY,
+                    species_id TEXT NOT NULL,
+                    event_type TEXT NOT NULL,
+                    timestamp TEXT NOT NULL,
+                    detail TEXT,
+                    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+                )
+            "#),
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_cultivation_events_species_time ON cultivation_events(species_id, timestamp)"),
+        ],
+    },
+    Migration {
+        version: 13,
+        description: "species collections",
+        steps: &[
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS collections (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    created_at TEXT NOT NULL
+                )
+            "#),
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS collection_members (
+                    collection_id TEXT NOT NULL,
+                    species_id TEXT NOT NULL,
+                    added_at TEXT NOT NULL,
+                    PRIMARY KEY (collection_id, species_id),
+                    FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE,
+                    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+                )
+            "#),
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_collection_members_species_id ON collection_members(species_id)"),
+        ],
+    },
+    Migration {
+        version: 14,
+        description: "ContextLite document mapping",
+        steps: &[
+            // No foreign key: the mapping must outlive its species until the
+            // document has been removed from ContextLite
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS contextlite_documents (
+                    species_id TEXT NOT NULL,
+                    workspace_id TEXT NOT NULL,
+                    document_id TEXT NOT NULL,
+                    indexed_at TEXT NOT NULL,
+                    pending_removal INTEGER NOT NULL DEFAULT 0,
+                    PRIMARY KEY (species_id, workspace_id)
+                )
+            "#),
+            Step::Sql(r#"
+                CREATE TRIGGER IF NOT EXISTS trg_contextlite_documents_species_deleted
+                AFTER DELETE ON species
+                BEGIN
+                    UPDATE contextlite_documents SET pending_removal = 1 WHERE species_id = OLD.id;
+                END
+            "#),
+        ],
+    },
+    Migration {
+        version: 15,
+        description: "species genus index",
+        steps: &[
+            // Serves species-per-genus lookups and counts, e.g. autocomplete genus sizes
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_species_genus_id ON species(genus_id)"),
+        ],
+    },
+    Migration {
+        version: 16,
+        description: "dataset write counters",
+        steps: &[
+            Step::Sql(r#"
+                CREATE TABLE IF NOT EXISTS dataset_versions (
+                    table_name TEXT PRIMARY KEY,
+                    version INTEGER NOT NULL DEFAULT 0
+                )
+            "#),
+            // Every table species-facing responses read from
+            Step::CountWrites { table: "families" },
+            Step::CountWrites { table: "genera" },
+            Step::CountWrites { table: "species" },
+            Step::CountWrites { table: "species_attributes" },
+            Step::CountWrites { table: "media" },
+            Step::CountWrites { table: "name_history" },
+            Step::CountWrites { table: "annotations" },
+            Step::CountWrites { table: "specimens" },
+            Step::CountWrites { table: "cultivation_events" },
+        ],
+    },
+];
+
 /// Initialize the database with all required tables
+///
+/// Safe to call from several processes at once: the whole run happens inside a
+/// `BEGIN IMMEDIATE` transaction, which takes SQLite's write lock up front. Other
+/// migrators wait on the busy timeout and, once the lock is released, find every
+/// version already recorded in `schema_migrations` and apply nothing.
 pub async fn run_migrations(pool: &SqlitePool) -> Result<(), DatabaseError> {
-    // Create families table
-    query(r#"
-        CREATE TABLE IF NOT EXISTS families (
-            id TEXT PRIMARY KEY,
-            name TEXT NOT NULL,
-            authority TEXT
-        )
-    "#)
-    .execute(pool)
-    .await?;
+    let mut tx = pool.begin_with("BEGIN IMMEDIATE").await?;
 
-    // Create genera table
     query(r#"
-        CREATE TABLE IF NOT EXISTS genera (
-            id TEXT PRIMARY KEY,
-            family_id TEXT NOT NULL,
-            name TEXT NOT NULL,
-            authority TEXT,
-            FOREIGN KEY (family_id) REFERENCES families(id)
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            applied_at TEXT NOT NULL
         )
     "#)
-    .execute(pool)
+    .execute(&mut *tx)
     .await?;
 
-    // Create species table
-    query(r#"
-        CREATE TABLE IF NOT EXISTS species (
-            id TEXT PRIMARY KEY,
-            genus_id TEXT NOT NULL,
-            specific_epithet TEXT NOT NULL,
-            authority TEXT,
-            publication_year INTEGER,
-            conservation_status TEXT,
-            FOREIGN KEY (genus_id) REFERENCES genera(id)
-        )
-    "#)
-    .execute(pool)
-    .await?;
+    let applied: Vec<i64> = query_scalar("SELECT version FROM schema_migrations")
+        .fetch_all(&mut *tx)
+        .await?;
 
-    // Create specimens table
-    query(r#"
-        CREATE TABLE IF NOT EXISTS specimens (
-            id TEXT PRIMARY KEY,
-            species_id TEXT NOT NULL,
-            collector TEXT,
-            collection_date TEXT,
-            location TEXT,
-            notes TEXT,
-            FOREIGN KEY (species_id) REFERENCES species(id)
-        )
-    "#)
-    .execute(pool)
-    .await?;
+    for migration in MIGRATIONS.iter().filter(|m| !applied.contains(&m.version)) {
+        for step in migration.steps {
+            apply_step(&mut tx, step).await.map_err(|e| {
+                DatabaseError::migration(format!("Migration {} ({}) failed: {}", migration.version, migration.description, e))
+            })?;
+        }
+
+        query(concat!("INSERT INTO schema_migrations (version, description, applied_at) VALUES (?, ?, ", now_rfc3339!(), ")"))
+            .bind(migration.version)
+            .bind(migration.description)
+            .execute(&mut *tx)
+            .await?;
+
+        log::info!("Applied migration {}: {}", migration.version, migration.description);
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn apply_step(conn: &mut SqliteConnection, step: &Step) -> Result<(), DatabaseError> {
+    match step {
+        Step::Sql(sql) => {
+            query(sql).execute(&mut *conn).await?;
+        }
+        Step::AddColumn { table, column, definition } => {
+            let exists = query("SELECT 1 FROM pragma_table_info(?) WHERE name = ?")
+                .bind(table)
+                .bind(column)
+                .fetch_optional(&mut *conn)
+                .await?
+                .is_some();
+
+            if !exists {
+                query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+                    .execute(&mut *conn)
+                    .await?;
+            }
+        }
+        Step::CountWrites { table } => {
+            query("INSERT OR IGNORE INTO dataset_versions (table_name, version) VALUES (?, 0)")
+                .bind(table)
+                .execute(&mut *conn)
+                .await?;
+
+            for operation in ["INSERT", "UPDATE", "DELETE"] {
+                query(&format!(
+                    "CREATE TRIGGER IF NOT EXISTS trg_{table}_{op}_version AFTER {operation} ON {table} \
+                     BEGIN UPDATE dataset_versions SET version = version + 1 WHERE table_name = '{table}'; END",
+                    table = table,
+                    op = operation.to_lowercase(),
+                    operation = operation,
+                ))
+                .execute(&mut *conn)
+                .await?;
+            }
+        }
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
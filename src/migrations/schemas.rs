@@ -1,11 +1,13 @@
-/// Database schema definitions
+//! Database schema definitions
 
 /// SQL for the families table
 pub const FAMILIES_TABLE_SQL: &str = r#"
 CREATE TABLE IF NOT EXISTS families (
     id TEXT PRIMARY KEY,
     name TEXT NOT NULL,
-    authority TEXT
+    authority TEXT,
+    created_at TEXT,
+    updated_at TEXT
 )
 "#;
 
@@ -16,6 +18,8 @@ CREATE TABLE IF NOT EXISTS genera (
     family_id TEXT NOT NULL,
     name TEXT NOT NULL,
     authority TEXT,
+    created_at TEXT,
+    updated_at TEXT,
     FOREIGN KEY (family_id) REFERENCES families(id)
 )
 "#;
@@ -29,6 +33,133 @@ CREATE TABLE IF NOT EXISTS species (
     authority TEXT NOT NULL,
     publication_year INTEGER,
     conservation_status TEXT,
+    source TEXT NOT NULL DEFAULT 'manual',
+    source_record_id TEXT,
+    created_at TEXT,
+    updated_at TEXT,
     FOREIGN KEY (genus_id) REFERENCES genera(id)
 )
-"#;
\ No newline at end of file
+"#;
+/// SQL for the species attributes table
+pub const SPECIES_ATTRIBUTES_TABLE_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS species_attributes (
+    species_id TEXT NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    updated_at TEXT,
+    PRIMARY KEY (species_id, key),
+    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+)
+"#;
+
+/// SQL for the media table
+pub const MEDIA_TABLE_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS media (
+    id TEXT PRIMARY KEY,
+    species_id TEXT NOT NULL,
+    url TEXT NOT NULL,
+    media_type TEXT NOT NULL,
+    license TEXT NOT NULL,
+    creator TEXT,
+    title TEXT,
+    created_at TEXT,
+    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+)
+"#;
+
+/// SQL for the species name history table
+pub const NAME_HISTORY_TABLE_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS name_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    species_id TEXT NOT NULL,
+    old_name TEXT NOT NULL,
+    new_name TEXT NOT NULL,
+    changed_at TEXT NOT NULL,
+    reason TEXT,
+    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+)
+"#;
+
+/// SQL for the per-year accession number counters
+pub const ACCESSION_SEQUENCES_TABLE_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS accession_sequences (
+    year INTEGER PRIMARY KEY,
+    last_value INTEGER NOT NULL
+)
+"#;
+
+/// SQL for the species annotations table
+pub const ANNOTATIONS_TABLE_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS annotations (
+    id TEXT PRIMARY KEY,
+    species_id TEXT NOT NULL,
+    author TEXT NOT NULL,
+    body TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    resolved INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+)
+"#;
+
+/// SQL for the species view statistics table
+pub const SPECIES_STATS_TABLE_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS species_stats (
+    species_id TEXT PRIMARY KEY,
+    view_count INTEGER NOT NULL DEFAULT 0,
+    last_viewed TEXT NOT NULL,
+    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+)
+"#;
+
+/// SQL for the cultivation events table
+pub const CULTIVATION_EVENTS_TABLE_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS cultivation_events (
+    id TEXT PRIMARY KEY,
+    species_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    timestamp TEXT NOT NULL,
+    detail TEXT,
+    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+)
+"#;
+
+/// SQL for the user-defined species collections table
+pub const COLLECTIONS_TABLE_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS collections (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    created_at TEXT NOT NULL
+)
+"#;
+
+/// SQL for the collection membership table
+pub const COLLECTION_MEMBERS_TABLE_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS collection_members (
+    collection_id TEXT NOT NULL,
+    species_id TEXT NOT NULL,
+    added_at TEXT NOT NULL,
+    PRIMARY KEY (collection_id, species_id),
+    FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE,
+    FOREIGN KEY (species_id) REFERENCES species(id) ON DELETE CASCADE
+)
+"#;
+
+/// SQL for the ContextLite document mapping table
+pub const CONTEXTLITE_DOCUMENTS_TABLE_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS contextlite_documents (
+    species_id TEXT NOT NULL,
+    workspace_id TEXT NOT NULL,
+    document_id TEXT NOT NULL,
+    indexed_at TEXT NOT NULL,
+    pending_removal INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (species_id, workspace_id)
+)
+"#;
+
+/// SQL for the per-table write counters behind the dataset ETag
+pub const DATASET_VERSIONS_TABLE_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS dataset_versions (
+    table_name TEXT PRIMARY KEY,
+    version INTEGER NOT NULL DEFAULT 0
+)
+"#;
@@ -0,0 +1,22 @@
+//! Convenience re-exports for applications
+//!
+//! `use botanica::prelude::*;` brings in the core types, the database handle
+//! and the everyday taxonomy queries. Less common queries stay in their
+//! `queries::*` modules.
+
+pub use crate::database::{BotanicalDatabase, DatabaseConfig};
+pub use crate::error::DatabaseError;
+pub use crate::types::{
+    Species, Genus, Family,
+    GrowthStage, Environment, CultivationRecord,
+    GeoPoint, Criterion, parse_iucn_criteria,
+};
+pub use crate::contextlite::BotanicalContext;
+
+pub use crate::queries::family::{insert_family, get_family_by_id, get_or_create_family, update_family, delete_family};
+pub use crate::queries::genus::{insert_genus, get_genus_by_id, get_genera_by_family_id, get_or_create_genus, update_genus, delete_genus};
+pub use crate::queries::species::{
+    insert_species, get_species_by_id, get_species_by_name, get_species_by_scientific_name,
+    update_species, delete_species,
+};
+pub use crate::{initialize_database, create_test_database};
@@ -0,0 +1,26 @@
+use sqlx::SqlitePool;
+use crate::error::DatabaseError;
+use crate::types::AccessionNumber;
+
+/// Allocate the next accession number for a year
+///
+/// The per-year counter is bumped by a single upsert, so concurrent callers,
+/// including other processes sharing the database file, never receive the
+/// same number. Numbers are not reused if the caller discards one.
+pub async fn next_accession_number(pool: &SqlitePool, year: i32) -> Result<AccessionNumber, DatabaseError> {
+    if !(0..=9999).contains(&year) {
+        return Err(DatabaseError::validation(format!("Accession year {} must have four digits", year)));
+    }
+    
+    let sequence: i64 = sqlx::query_scalar(
+        "INSERT INTO accession_sequences (year, last_value) VALUES (?, 1) \
+         ON CONFLICT (year) DO UPDATE SET last_value = last_value + 1 RETURNING last_value"
+    )
+    .bind(year)
+    .fetch_one(pool)
+    .await?;
+    
+    let sequence = u32::try_from(sequence)
+        .map_err(|_| DatabaseError::validation(format!("Accession sequence for {} is exhausted", year)))?;
+    Ok(AccessionNumber::new(year, sequence))
+}
@@ -0,0 +1,69 @@
+use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
+use uuid::Uuid;
+use crate::error::DatabaseError;
+use crate::types::Annotation;
+
+/// Add an annotation to a species
+pub async fn add_annotation(pool: &SqlitePool, annotation: &Annotation) -> Result<(), DatabaseError> {
+    if annotation.body.trim().is_empty() {
+        return Err(DatabaseError::validation("Annotation body must not be empty"));
+    }
+    
+    sqlx::query("INSERT INTO annotations (id, species_id, author, body, created_at, resolved) VALUES (?, ?, ?, ?, ?, ?)")
+        .bind(annotation.id.to_string())
+        .bind(annotation.species_id.to_string())
+        .bind(&annotation.author)
+        .bind(&annotation.body)
+        .bind(annotation.created_at)
+        .bind(annotation.resolved)
+        .execute(pool)
+        .await?;
+    
+    Ok(())
+}
+
+/// Get all annotations on a species, resolved or not, oldest first
+pub async fn get_annotations(pool: &SqlitePool, species_id: Uuid) -> Result<Vec<Annotation>, DatabaseError> {
+    let rows = sqlx::query("SELECT id, species_id, author, body, created_at, resolved FROM annotations WHERE species_id = ? ORDER BY created_at, id")
+        .bind(species_id.to_string())
+        .fetch_all(pool)
+        .await?;
+    
+    rows.iter().map(annotation_from_row).collect()
+}
+
+/// Get unresolved annotations across all species, oldest first, as a curation work queue
+pub async fn get_open_annotations(pool: &SqlitePool) -> Result<Vec<Annotation>, DatabaseError> {
+    let rows = sqlx::query("SELECT id, species_id, author, body, created_at, resolved FROM annotations WHERE resolved = 0 ORDER BY created_at, id")
+        .fetch_all(pool)
+        .await?;
+    
+    rows.iter().map(annotation_from_row).collect()
+}
+
+/// Mark an annotation resolved
+///
+/// Returns `false` if no such annotation exists or it was already resolved.
+pub async fn resolve_annotation(pool: &SqlitePool, id: Uuid) -> Result<bool, DatabaseError> {
+    let result = sqlx::query("UPDATE annotations SET resolved = 1 WHERE id = ? AND resolved = 0")
+        .bind(id.to_string())
+        .execute(pool)
+        .await?;
+    
+    Ok(result.rows_affected() > 0)
+}
+
+fn annotation_from_row(row: &SqliteRow) -> Result<Annotation, DatabaseError> {
+    let id_str: String = row.get("id");
+    let species_id_str: String = row.get("species_id");
+    
+    Ok(Annotation {
+        id: Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+        species_id: Uuid::parse_str(&species_id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+        author: row.get("author"),
+        body: row.get("body"),
+        created_at: row.get("created_at"),
+        resolved: row.get("resolved"),
+    })
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use sqlx::{SqlitePool, Row};
+use uuid::Uuid;
+use chrono::Utc;
+use crate::error::DatabaseError;
+use crate::types::Species;
+use super::species::species_from_row;
+
+/// Set a free-form attribute on a species, replacing any previous value for the key
+///
+/// Keys are trimmed and must not be empty. Attributes are deleted along with
+/// their species.
+pub async fn set_species_attribute(pool: &SqlitePool, species_id: Uuid, key: &str, value: &str) -> Result<(), DatabaseError> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(DatabaseError::validation("Attribute key must not be empty"));
+    }
+    
+    sqlx::query(
+        "INSERT INTO species_attributes (species_id, key, value, updated_at) VALUES (?, ?, ?, ?) \
+         ON CONFLICT (species_id, key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at"
+    )
+    .bind(species_id.to_string())
+    .bind(key)
+    .bind(value)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+    
+    Ok(())
+}
+
+/// Get all attributes of a species as a key/value map
+pub async fn get_species_attributes(pool: &SqlitePool, species_id: Uuid) -> Result<HashMap<String, String>, DatabaseError> {
+    let rows = sqlx::query("SELECT key, value FROM species_attributes WHERE species_id = ?")
+        .bind(species_id.to_string())
+        .fetch_all(pool)
+        .await?;
+    
+    Ok(rows.iter().map(|row| (row.get("key"), row.get("value"))).collect())
+}
+
+/// Find species whose attribute `key` has exactly the given value
+///
+/// Served from the `(key, value)` index.
+pub async fn find_species_by_attribute(pool: &SqlitePool, key: &str, value: &str) -> Result<Vec<Species>, DatabaseError> {
+    let rows = sqlx::query(
+        "SELECT s.id, s.genus_id, s.specific_epithet, s.authority, s.publication_year, s.conservation_status, s.source, s.source_record_id \
+         FROM species_attributes a JOIN species s ON a.species_id = s.id \
+         WHERE a.key = ? AND a.value = ? ORDER BY s.specific_epithet, s.id"
+    )
+    .bind(key.trim())
+    .bind(value)
+    .fetch_all(pool)
+    .await?;
+    
+    rows.iter().map(species_from_row).collect()
+}
@@ -0,0 +1,97 @@
+use chrono::Utc;
+use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
+use uuid::Uuid;
+use crate::error::DatabaseError;
+use crate::types::{Collection, Species};
+use super::species::species_from_row;
+
+/// Create a species collection with the given name
+///
+/// The name is trimmed and must not be empty; names need not be unique.
+pub async fn create_collection(pool: &SqlitePool, name: &str) -> Result<Collection, DatabaseError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DatabaseError::validation("Collection name must not be empty"));
+    }
+    
+    let collection = Collection::new(name.to_string());
+    sqlx::query("INSERT INTO collections (id, name, created_at) VALUES (?, ?, ?)")
+        .bind(collection.id.to_string())
+        .bind(&collection.name)
+        .bind(collection.created_at)
+        .execute(pool)
+        .await?;
+    
+    Ok(collection)
+}
+
+/// Add a species to a collection
+///
+/// Returns `false` if the species was already a member. Fails with a foreign
+/// key error if either the collection or the species does not exist.
+pub async fn add_to_collection(pool: &SqlitePool, collection_id: Uuid, species_id: Uuid) -> Result<bool, DatabaseError> {
+    let result = sqlx::query(
+        "INSERT INTO collection_members (collection_id, species_id, added_at) VALUES (?, ?, ?) \
+         ON CONFLICT (collection_id, species_id) DO NOTHING"
+    )
+    .bind(collection_id.to_string())
+    .bind(species_id.to_string())
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+    
+    Ok(result.rows_affected() > 0)
+}
+
+/// Remove a species from a collection
+///
+/// Returns `false` if the species was not a member.
+pub async fn remove_from_collection(pool: &SqlitePool, collection_id: Uuid, species_id: Uuid) -> Result<bool, DatabaseError> {
+    let result = sqlx::query("DELETE FROM collection_members WHERE collection_id = ? AND species_id = ?")
+        .bind(collection_id.to_string())
+        .bind(species_id.to_string())
+        .execute(pool)
+        .await?;
+    
+    Ok(result.rows_affected() > 0)
+}
+
+/// Get the species in a collection, in the order they were added
+pub async fn get_collection_species(pool: &SqlitePool, collection_id: Uuid) -> Result<Vec<Species>, DatabaseError> {
+    let rows = sqlx::query(
+        "SELECT s.id, s.genus_id, s.specific_epithet, s.authority, s.publication_year, s.conservation_status, s.source, s.source_record_id \
+         FROM collection_members m JOIN species s ON s.id = m.species_id \
+         WHERE m.collection_id = ? \
+         ORDER BY m.added_at, m.rowid"
+    )
+    .bind(collection_id.to_string())
+    .fetch_all(pool)
+    .await?;
+    
+    rows.iter().map(species_from_row).collect()
+}
+
+/// Get the collections a species belongs to, by name
+pub async fn get_species_collections(pool: &SqlitePool, species_id: Uuid) -> Result<Vec<Collection>, DatabaseError> {
+    let rows = sqlx::query(
+        "SELECT c.id, c.name, c.created_at FROM collections c \
+         JOIN collection_members m ON m.collection_id = c.id \
+         WHERE m.species_id = ? ORDER BY c.name COLLATE NOCASE, c.id"
+    )
+    .bind(species_id.to_string())
+    .fetch_all(pool)
+    .await?;
+    
+    rows.iter().map(collection_from_row).collect()
+}
+
+fn collection_from_row(row: &SqliteRow) -> Result<Collection, DatabaseError> {
+    let id_str: String = row.get("id");
+    
+    Ok(Collection {
+        id: Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+        name: row.get("name"),
+        created_at: row.get("created_at"),
+    })
+}
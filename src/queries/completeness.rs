@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+use sqlx::{SqlitePool, Row};
+use uuid::Uuid;
+use crate::error::DatabaseError;
+use crate::types::{Family, Genus, Species};
+use super::species::species_from_row;
+
+/// The genus and family a species is classified under, where known
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lineage<'a> {
+    /// The species' genus
+    pub genus: Option<&'a Genus>,
+    
+    /// The genus's family
+    pub family: Option<&'a Family>,
+}
+
+/// Relative weight of each completeness component
+///
+/// The default weighs every component equally at 25:
+///
+/// - `authority`: a non-empty authority
+/// - `publication_year`: a publication year
+/// - `lineage`: half for a genus matching the species' `genus_id`, half for a
+///   family matching that genus's `family_id`
+/// - `conservation_status`: a non-empty conservation status
+///
+/// Weights are relative; scores are scaled to 0-100 whatever they sum to.
+/// Common names are not stored by the crate and so do not contribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompletenessWeights {
+    /// Weight of a non-empty authority
+    pub authority: u32,
+    
+    /// Weight of a publication year
+    pub publication_year: u32,
+    
+    /// Weight of a resolved genus and family
+    pub lineage: u32,
+    
+    /// Weight of a conservation status
+    pub conservation_status: u32,
+}
+
+impl Default for CompletenessWeights {
+    fn default() -> Self {
+        Self {
+            authority: 25,
+            publication_year: 25,
+            lineage: 25,
+            conservation_status: 25,
+        }
+    }
+}
+
+/// How complete a species record is
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletenessScore {
+    /// Weighted completeness from 0 (nothing) to 100 (everything)
+    pub score: f32,
+    
+    /// Components that are missing or only partly present
+    pub missing: Vec<&'static str>,
+}
+
+/// Score a species record's completeness using the default weights
+pub fn taxonomic_completeness(species: &Species, lineage: Lineage<'_>) -> CompletenessScore {
+    taxonomic_completeness_with(species, lineage, &CompletenessWeights::default())
+}
+
+/// Score a species record's completeness using custom weights
+pub fn taxonomic_completeness_with(species: &Species, lineage: Lineage<'_>, weights: &CompletenessWeights) -> CompletenessScore {
+    let genus = lineage.genus.filter(|g| g.id == species.genus_id);
+    let family = lineage.family.filter(|f| genus.is_some_and(|g| g.family_id == f.id));
+    let lineage_fraction = (genus.is_some() as u8 + family.is_some() as u8) as f32 / 2.0;
+    
+    let components = [
+        ("authority", weights.authority, if species.authority.trim().is_empty() { 0.0 } else { 1.0 }),
+        ("publication_year", weights.publication_year, if species.publication_year.is_some() { 1.0 } else { 0.0 }),
+        ("lineage", weights.lineage, lineage_fraction),
+        (
+            "conservation_status",
+            weights.conservation_status,
+            if species.conservation_status.as_deref().is_some_and(|s| !s.trim().is_empty()) { 1.0 } else { 0.0 },
+        ),
+    ];
+    
+    let total: u32 = components.iter().map(|(_, weight, _)| weight).sum();
+    let earned: f32 = components.iter().map(|(_, weight, fraction)| *weight as f32 * fraction).sum();
+    let missing = components
+        .iter()
+        .filter(|(_, weight, fraction)| *weight > 0 && *fraction < 1.0)
+        .map(|(name, _, _)| *name)
+        .collect();
+    
+    CompletenessScore {
+        score: if total == 0 { 100.0 } else { earned * 100.0 / total as f32 },
+        missing,
+    }
+}
+
+/// Average completeness of the species in one family
+#[derive(Debug, Clone, PartialEq)]
+pub struct FamilyCompleteness {
+    /// The family, or `None` for species whose genus or family is missing
+    pub family_id: Option<Uuid>,
+    
+    /// The family name, or `None` alongside `family_id`
+    pub family_name: Option<String>,
+    
+    /// Number of species scored
+    pub species_count: usize,
+    
+    /// Mean completeness score of those species
+    pub average_score: f32,
+}
+
+/// Sorts placed families by name, then unplaced species last
+type FamilyKey = (bool, Option<String>, Option<Uuid>);
+
+/// Average completeness per family using the default weights
+pub async fn completeness_report(pool: &SqlitePool) -> Result<Vec<FamilyCompleteness>, DatabaseError> {
+    completeness_report_with(pool, &CompletenessWeights::default()).await
+}
+
+/// Average completeness per family using custom weights
+///
+/// Families are sorted by name; species that cannot be placed in a family are
+/// reported last under `family_id: None`. Families without species are omitted.
+pub async fn completeness_report_with(pool: &SqlitePool, weights: &CompletenessWeights) -> Result<Vec<FamilyCompleteness>, DatabaseError> {
+    let rows = sqlx::query(
+        "SELECT s.id, s.genus_id, s.specific_epithet, s.authority, s.publication_year, s.conservation_status, s.source, s.source_record_id, \
+                g.id AS g_id, g.family_id AS g_family_id, g.name AS g_name, g.authority AS g_authority, \
+                f.id AS f_id, f.name AS f_name, f.authority AS f_authority \
+         FROM species s \
+         LEFT JOIN genera g ON s.genus_id = g.id \
+         LEFT JOIN families f ON g.family_id = f.id"
+    )
+    .fetch_all(pool)
+    .await?;
+    
+    let mut totals: BTreeMap<FamilyKey, (usize, f32)> = BTreeMap::new();
+    for row in &rows {
+        let species = species_from_row(row)?;
+        let genus = match row.get::<Option<String>, _>("g_id") {
+            Some(id) => Some(Genus::with_id(
+                parse_id(&id)?,
+                parse_id(&row.get::<String, _>("g_family_id"))?,
+                row.get("g_name"),
+                row.get::<Option<String>, _>("g_authority").unwrap_or_default(),
+            )),
+            None => None,
+        };
+        let family = match row.get::<Option<String>, _>("f_id") {
+            Some(id) => Some(Family::with_id(
+                parse_id(&id)?,
+                row.get("f_name"),
+                row.get::<Option<String>, _>("f_authority").unwrap_or_default(),
+            )),
+            None => None,
+        };
+        
+        let score = taxonomic_completeness_with(&species, Lineage { genus: genus.as_ref(), family: family.as_ref() }, weights);
+        let key = match family {
+            Some(family) => (false, Some(family.name), Some(family.id)),
+            None => (true, None, None),
+        };
+        let entry = totals.entry(key).or_insert((0, 0.0));
+        entry.0 += 1;
+        entry.1 += score.score;
+    }
+    
+    Ok(totals
+        .into_iter()
+        .map(|((_, family_name, family_id), (count, sum))| FamilyCompleteness {
+            family_id,
+            family_name,
+            species_count: count,
+            average_score: sum / count as f32,
+        })
+        .collect())
+}
+
+fn parse_id(id: &str) -> Result<Uuid, DatabaseError> {
+    Uuid::parse_str(id).map_err(|e| DatabaseError::validation(e.to_string()))
+}
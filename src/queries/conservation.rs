@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use sqlx::{SqlitePool, Row};
+use crate::error::DatabaseError;
+use crate::types::IUCNCategory;
+
+/// Count stored species by IUCN category
+///
+/// Species with no conservation status are counted as `NotEvaluated`, as are
+/// statuses that are not a Red List code (logged as a warning). Categories with
+/// no species are absent from the map.
+pub async fn conservation_breakdown(pool: &SqlitePool) -> Result<HashMap<IUCNCategory, i64>, DatabaseError> {
+    let rows = sqlx::query(
+        "SELECT upper(trim(COALESCE(conservation_status, ''))) AS status, COUNT(*) AS species_count \
+         FROM species GROUP BY status"
+    )
+    .fetch_all(pool)
+    .await?;
+    
+    let mut breakdown = HashMap::new();
+    for row in rows {
+        let status: String = row.get("status");
+        let count: i64 = row.get("species_count");
+        let category = if status.is_empty() {
+            IUCNCategory::NotEvaluated
+        } else {
+            status.parse().unwrap_or_else(|_| {
+                log::warn!("Counting unrecognised conservation status '{}' as not evaluated", status);
+                IUCNCategory::NotEvaluated
+            })
+        };
+        *breakdown.entry(category).or_insert(0) += count;
+    }
+    
+    Ok(breakdown)
+}
+
+/// Convert a breakdown into percentages of the total, summing to 100
+///
+/// Returns an empty map when the breakdown holds no species.
+pub fn breakdown_percentages(breakdown: &HashMap<IUCNCategory, i64>) -> HashMap<IUCNCategory, f64> {
+    let total: i64 = breakdown.values().sum();
+    if total == 0 {
+        return HashMap::new();
+    }
+    
+    breakdown.iter()
+        .map(|(category, count)| (*category, *count as f64 * 100.0 / total as f64))
+        .collect()
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
+use uuid::Uuid;
+use crate::error::DatabaseError;
+
+/// The ContextLite document a species is indexed under in one workspace
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextDocumentRecord {
+    /// The indexed species; may no longer exist if `pending_removal` is set
+    pub species_id: Uuid,
+    
+    /// ContextLite workspace holding the document
+    pub workspace_id: String,
+    
+    /// ContextLite document ID
+    pub document_id: String,
+    
+    /// When the document was last indexed
+    pub indexed_at: DateTime<Utc>,
+    
+    /// Set when the species is deleted; the document should be removed from ContextLite
+    pub pending_removal: bool,
+}
+
+/// Record that a species is indexed under a document, replacing any previous mapping
+pub async fn record_context_document(
+    pool: &SqlitePool,
+    species_id: Uuid,
+    workspace_id: &str,
+    document_id: &str,
+) -> Result<(), DatabaseError> {
+    sqlx::query(
+        "INSERT INTO contextlite_documents (species_id, workspace_id, document_id, indexed_at, pending_removal) VALUES (?, ?, ?, ?, 0) \
+         ON CONFLICT (species_id, workspace_id) DO UPDATE SET \
+         document_id = excluded.document_id, indexed_at = excluded.indexed_at, pending_removal = 0"
+    )
+    .bind(species_id.to_string())
+    .bind(workspace_id)
+    .bind(document_id)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+    
+    Ok(())
+}
+
+/// Get the document a species is indexed under in a workspace
+pub async fn get_context_document(
+    pool: &SqlitePool,
+    species_id: Uuid,
+    workspace_id: &str,
+) -> Result<Option<ContextDocumentRecord>, DatabaseError> {
+    let row = sqlx::query(
+        "SELECT species_id, workspace_id, document_id, indexed_at, pending_removal FROM contextlite_documents \
+         WHERE species_id = ? AND workspace_id = ?"
+    )
+    .bind(species_id.to_string())
+    .bind(workspace_id)
+    .fetch_optional(pool)
+    .await?;
+    
+    row.as_ref().map(record_from_row).transpose()
+}
+
+/// Get the documents in a workspace whose species have been deleted
+///
+/// Deleting a species marks its mappings by trigger, so no code path that
+/// deletes species can leave documents untracked.
+pub async fn get_documents_pending_removal(pool: &SqlitePool, workspace_id: &str) -> Result<Vec<ContextDocumentRecord>, DatabaseError> {
+    let rows = sqlx::query(
+        "SELECT species_id, workspace_id, document_id, indexed_at, pending_removal FROM contextlite_documents \
+         WHERE workspace_id = ? AND pending_removal = 1 ORDER BY indexed_at, species_id"
+    )
+    .bind(workspace_id)
+    .fetch_all(pool)
+    .await?;
+    
+    rows.iter().map(record_from_row).collect()
+}
+
+/// Forget the mapping of a species in a workspace, once its document is gone
+pub async fn forget_context_document(pool: &SqlitePool, species_id: Uuid, workspace_id: &str) -> Result<bool, DatabaseError> {
+    let result = sqlx::query("DELETE FROM contextlite_documents WHERE species_id = ? AND workspace_id = ?")
+        .bind(species_id.to_string())
+        .bind(workspace_id)
+        .execute(pool)
+        .await?;
+    
+    Ok(result.rows_affected() > 0)
+}
+
+fn record_from_row(row: &SqliteRow) -> Result<ContextDocumentRecord, DatabaseError> {
+    let species_id_str: String = row.get("species_id");
+    
+    Ok(ContextDocumentRecord {
+        species_id: Uuid::parse_str(&species_id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+        workspace_id: row.get("workspace_id"),
+        document_id: row.get("document_id"),
+        indexed_at: row.get("indexed_at"),
+        pending_removal: row.get("pending_removal"),
+    })
+}
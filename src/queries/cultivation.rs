@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
+use uuid::Uuid;
+use crate::error::DatabaseError;
+use crate::types::CultivationEvent;
+
+/// Record a cultivation event on a species
+pub async fn add_cultivation_event(pool: &SqlitePool, event: &CultivationEvent) -> Result<(), DatabaseError> {
+    sqlx::query("INSERT INTO cultivation_events (id, species_id, event_type, timestamp, detail) VALUES (?, ?, ?, ?, ?)")
+        .bind(event.id.to_string())
+        .bind(event.species_id.to_string())
+        .bind(event.event_type.to_string())
+        .bind(event.timestamp)
+        .bind(&event.detail)
+        .execute(pool)
+        .await?;
+    
+    Ok(())
+}
+
+/// Get the events on a species between `from` and `to` inclusive, oldest first
+pub async fn get_cultivation_events(
+    pool: &SqlitePool,
+    species_id: Uuid,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<CultivationEvent>, DatabaseError> {
+    let rows = sqlx::query(
+        "SELECT id, species_id, event_type, timestamp, detail FROM cultivation_events \
+         WHERE species_id = ? AND timestamp >= ? AND timestamp <= ? ORDER BY timestamp, id"
+    )
+    .bind(species_id.to_string())
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+    
+    rows.iter().map(event_from_row).collect()
+}
+
+/// Get the latest `limit` events on a species, oldest first
+pub async fn get_recent_cultivation_events(pool: &SqlitePool, species_id: Uuid, limit: usize) -> Result<Vec<CultivationEvent>, DatabaseError> {
+    let rows = sqlx::query(
+        "SELECT id, species_id, event_type, timestamp, detail FROM cultivation_events \
+         WHERE species_id = ? ORDER BY timestamp DESC, id DESC LIMIT ?"
+    )
+    .bind(species_id.to_string())
+    .bind(limit as i64)
+    .fetch_all(pool)
+    .await?;
+    
+    rows.iter().rev().map(event_from_row).collect()
+}
+
+fn event_from_row(row: &SqliteRow) -> Result<CultivationEvent, DatabaseError> {
+    let id_str: String = row.get("id");
+    let species_id_str: String = row.get("species_id");
+    let event_type: String = row.get("event_type");
+    
+    Ok(CultivationEvent {
+        id: Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+        species_id: Uuid::parse_str(&species_id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+        event_type: event_type.parse()?,
+        timestamp: row.get("timestamp"),
+        detail: row.get("detail"),
+    })
+}
@@ -0,0 +1,33 @@
+use sqlx::{SqlitePool, Row};
+use crate::error::DatabaseError;
+
+/// Compute a cheap fingerprint of the species data, suitable for an HTTP `ETag`
+///
+/// Hashes the write counters that triggers keep in `dataset_versions` for
+/// families, genera, species and the species' attributes, media, name history,
+/// annotations, specimens and cultivation events. Two calls with no writes in
+/// between return the same value, while any insert, update or delete on those
+/// tables changes it, including rows removed by cascade. The hash (64-bit
+/// FNV-1a) is stable across builds, so values can be compared between
+/// processes. Returned as 16 hex digits without the quotes an `ETag` header
+/// needs.
+pub async fn dataset_etag(pool: &SqlitePool) -> Result<String, DatabaseError> {
+    let rows = sqlx::query("SELECT table_name, version FROM dataset_versions ORDER BY table_name")
+        .fetch_all(pool)
+        .await?;
+    
+    let mut summary = String::new();
+    for row in rows {
+        let table: String = row.get("table_name");
+        let version: i64 = row.get("version");
+        summary.push_str(&format!("{}:{};", table, version));
+    }
+    
+    Ok(format!("{:016x}", fnv1a_64(summary.as_bytes())))
+}
+
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -1,20 +1,68 @@
 use sqlx::{SqlitePool, Row};
 use uuid::Uuid;
+use chrono::Utc;
 use crate::error::DatabaseError;
-use crate::types::Family;
+use crate::types::{Family, IdStrategy, RandomIdStrategy};
 
 /// Insert a new family into the database
 pub async fn insert_family(pool: &SqlitePool, family: &Family) -> Result<(), DatabaseError> {
-    sqlx::query("INSERT INTO families (id, name, authority) VALUES (?, ?, ?)")
+    let now = Utc::now();
+    sqlx::query("INSERT INTO families (id, name, authority, created_at, updated_at) VALUES (?, ?, ?, ?, ?)")
         .bind(family.id.to_string())
         .bind(&family.name)
         .bind(&family.authority)
+        .bind(now)
+        .bind(now)
         .execute(pool)
         .await?;
     
     Ok(())
 }
 
+/// Get the ID of a family by name and authority, inserting it if it does not exist
+///
+/// Safe under concurrent imports: the insert is a single `ON CONFLICT DO NOTHING`
+/// statement, and a task that loses the race falls back to selecting the winner's row.
+/// Returns the family ID and whether a new row was created.
+pub async fn get_or_create_family(pool: &SqlitePool, name: &str, authority: &str) -> Result<(Uuid, bool), DatabaseError> {
+    get_or_create_family_with_ids(pool, name, authority, &RandomIdStrategy).await
+}
+
+/// Like `get_or_create_family`, taking the ID of a newly created family from `ids`
+pub async fn get_or_create_family_with_ids(
+    pool: &SqlitePool,
+    name: &str,
+    authority: &str,
+    ids: &dyn IdStrategy,
+) -> Result<(Uuid, bool), DatabaseError> {
+    let now = Utc::now();
+    let inserted = sqlx::query(
+        "INSERT INTO families (id, name, authority, created_at, updated_at) VALUES (?, ?, ?, ?, ?) \
+         ON CONFLICT (name, authority) DO NOTHING RETURNING id"
+    )
+    .bind(ids.next_id().to_string())
+    .bind(name)
+    .bind(authority)
+    .bind(now)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+    
+    if let Some(row) = inserted {
+        let id_str: String = row.get("id");
+        return Ok((Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?, true));
+    }
+    
+    let row = sqlx::query("SELECT id FROM families WHERE name = ? AND authority = ?")
+        .bind(name)
+        .bind(authority)
+        .fetch_one(pool)
+        .await?;
+    
+    let id_str: String = row.get("id");
+    Ok((Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?, false))
+}
+
 /// Get a family by ID
 pub async fn get_family_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Family>, DatabaseError> {
     let row = sqlx::query("SELECT id, name, authority FROM families WHERE id = ?")
@@ -62,9 +110,10 @@ pub async fn get_families_by_name(pool: &SqlitePool, name: &str) -> Result<Vec<F
 
 /// Update a family
 pub async fn update_family(pool: &SqlitePool, id: Uuid, family: &Family) -> Result<bool, DatabaseError> {
-    let result = sqlx::query("UPDATE families SET name = ?, authority = ? WHERE id = ?")
+    let result = sqlx::query("UPDATE families SET name = ?, authority = ?, updated_at = ? WHERE id = ?")
         .bind(&family.name)
         .bind(&family.authority)
+        .bind(Utc::now())
         .bind(id.to_string())
         .execute(pool)
         .await?;
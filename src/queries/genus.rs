@@ -1,21 +1,77 @@
 use sqlx::{SqlitePool, Row};
 use uuid::Uuid;
+use chrono::Utc;
 use crate::error::DatabaseError;
-use crate::types::Genus;
+use crate::queries::species::{record_name_change, scientific_name_of};
+use crate::types::{Genus, IdStrategy, RandomIdStrategy};
 
 /// Insert a new genus into the database
 pub async fn insert_genus(pool: &SqlitePool, genus: &Genus) -> Result<(), DatabaseError> {
-    sqlx::query("INSERT INTO genera (id, family_id, name, authority) VALUES (?, ?, ?, ?)")
+    let now = Utc::now();
+    sqlx::query("INSERT INTO genera (id, family_id, name, authority, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)")
         .bind(genus.id.to_string())
         .bind(genus.family_id.to_string())
         .bind(&genus.name)
         .bind(&genus.authority)
+        .bind(now)
+        .bind(now)
         .execute(pool)
         .await?;
     
     Ok(())
 }
 
+/// Get the ID of a genus by family, name and authority, inserting it if it does not exist
+///
+/// Safe under concurrent imports in the same way as `get_or_create_family`.
+/// Returns the genus ID and whether a new row was created.
+pub async fn get_or_create_genus(
+    pool: &SqlitePool,
+    family_id: Uuid,
+    name: &str,
+    authority: &str,
+) -> Result<(Uuid, bool), DatabaseError> {
+    get_or_create_genus_with_ids(pool, family_id, name, authority, &RandomIdStrategy).await
+}
+
+/// Like `get_or_create_genus`, taking the ID of a newly created genus from `ids`
+pub async fn get_or_create_genus_with_ids(
+    pool: &SqlitePool,
+    family_id: Uuid,
+    name: &str,
+    authority: &str,
+    ids: &dyn IdStrategy,
+) -> Result<(Uuid, bool), DatabaseError> {
+    let now = Utc::now();
+    let inserted = sqlx::query(
+        "INSERT INTO genera (id, family_id, name, authority, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?) \
+         ON CONFLICT (family_id, name, authority) DO NOTHING RETURNING id"
+    )
+    .bind(ids.next_id().to_string())
+    .bind(family_id.to_string())
+    .bind(name)
+    .bind(authority)
+    .bind(now)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+    
+    if let Some(row) = inserted {
+        let id_str: String = row.get("id");
+        return Ok((Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?, true));
+    }
+    
+    let row = sqlx::query("SELECT id FROM genera WHERE family_id = ? AND name = ? AND authority = ?")
+        .bind(family_id.to_string())
+        .bind(name)
+        .bind(authority)
+        .fetch_one(pool)
+        .await?;
+    
+    let id_str: String = row.get("id");
+    Ok((Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?, false))
+}
+
 /// Get a genus by ID
 pub async fn get_genus_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Genus>, DatabaseError> {
     let row = sqlx::query("SELECT id, family_id, name, authority FROM genera WHERE id = ?")
@@ -67,10 +123,11 @@ pub async fn get_genera_by_family_id(pool: &SqlitePool, family_id: Uuid) -> Resu
 
 /// Update a genus
 pub async fn update_genus(pool: &SqlitePool, id: Uuid, genus: &Genus) -> Result<bool, DatabaseError> {
-    let result = sqlx::query("UPDATE genera SET family_id = ?, name = ?, authority = ? WHERE id = ?")
+    let result = sqlx::query("UPDATE genera SET family_id = ?, name = ?, authority = ?, updated_at = ? WHERE id = ?")
         .bind(genus.family_id.to_string())
         .bind(&genus.name)
         .bind(&genus.authority)
+        .bind(Utc::now())
         .bind(id.to_string())
         .execute(pool)
         .await?;
@@ -78,6 +135,56 @@ pub async fn update_genus(pool: &SqlitePool, id: Uuid, genus: &Genus) -> Result<
     Ok(result.rows_affected() > 0)
 }
 
+/// Rename a genus, recording the new binomial of each of its species in the name history
+///
+/// Scientific names are derived from `genus_id`, so only the genus row changes;
+/// the history entries are written in the same transaction.
+/// Returns false if the genus does not exist.
+pub async fn rename_genus(pool: &SqlitePool, genus_id: Uuid, new_name: &str) -> Result<bool, DatabaseError> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err(DatabaseError::validation("Genus name must not be empty"));
+    }
+    
+    let mut tx = pool.begin_with("BEGIN IMMEDIATE").await?;
+    let species_ids: Vec<String> = sqlx::query_scalar("SELECT id FROM species WHERE genus_id = ? ORDER BY rowid")
+        .bind(genus_id.to_string())
+        .fetch_all(&mut *tx)
+        .await?;
+    let species_ids = species_ids.iter()
+        .map(|id| Uuid::parse_str(id).map_err(|e| DatabaseError::validation(e.to_string())))
+        .collect::<Result<Vec<_>, _>>()?;
+    
+    let mut old_names = Vec::with_capacity(species_ids.len());
+    for id in &species_ids {
+        old_names.push(scientific_name_of(&mut tx, *id).await?);
+    }
+    
+    let result = sqlx::query("UPDATE genera SET name = ?, updated_at = ? WHERE id = ?")
+        .bind(new_name)
+        .bind(Utc::now())
+        .bind(genus_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+    
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+    
+    let reason = format!("Genus renamed to {}", new_name);
+    for (id, old_name) in species_ids.into_iter().zip(old_names) {
+        let new_species_name = scientific_name_of(&mut tx, id).await?;
+        if let (Some(old_name), Some(new_species_name)) = (old_name, new_species_name) {
+            if old_name != new_species_name {
+                record_name_change(&mut tx, id, &old_name, &new_species_name, Some(&reason)).await?;
+            }
+        }
+    }
+    
+    tx.commit().await?;
+    Ok(true)
+}
+
 /// Delete a genus
 pub async fn delete_genus(pool: &SqlitePool, id: Uuid) -> Result<bool, DatabaseError> {
     let result = sqlx::query("DELETE FROM genera WHERE id = ?")
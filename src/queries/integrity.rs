@@ -0,0 +1,425 @@
+use sqlx::{SqlitePool, SqliteConnection, Row};
+use uuid::Uuid;
+use chrono::Utc;
+use crate::error::DatabaseError;
+
+/// Name of the placeholder family and genus that orphaned taxa are moved under
+pub const INCERTAE_SEDIS: &str = "Incertae sedis";
+
+/// Category of data integrity problem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FindingKind {
+    /// Species whose genus does not exist
+    OrphanedSpecies,
+    /// Genus whose family does not exist
+    OrphanedGenus,
+    /// Specimen whose species does not exist
+    OrphanedSpecimen,
+    /// Family with the same name and authority as another, ignoring case and whitespace
+    DuplicateFamily,
+    /// Genus duplicating another in the same family
+    DuplicateGenus,
+    /// Species duplicating another in the same genus
+    DuplicateSpecies,
+}
+
+/// A single data integrity problem found by `audit_integrity`
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntegrityFinding {
+    /// What kind of problem this is
+    pub kind: FindingKind,
+
+    /// The record with the problem
+    pub affected_id: Uuid,
+
+    /// For orphans, the missing parent ID; for duplicates, the record it duplicates
+    pub related_id: Option<Uuid>,
+
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Check the database for orphaned records and duplicate taxa
+///
+/// Read-only: findings are reported, never fixed. Orphans can only appear in
+/// databases written with foreign key enforcement off. For duplicates, the
+/// earliest-created record of each group (insertion order breaks ties) is
+/// treated as the original and every other member is reported against it.
+pub async fn audit_integrity(pool: &SqlitePool) -> Result<Vec<IntegrityFinding>, DatabaseError> {
+    let mut findings = Vec::new();
+
+    let orphan_checks = [
+        (
+            FindingKind::OrphanedSpecies,
+            "SELECT s.id, s.genus_id AS parent_id FROM species s \
+             LEFT JOIN genera g ON s.genus_id = g.id WHERE g.id IS NULL ORDER BY s.id",
+            "Species references missing genus",
+        ),
+        (
+            FindingKind::OrphanedGenus,
+            "SELECT g.id, g.family_id AS parent_id FROM genera g \
+             LEFT JOIN families f ON g.family_id = f.id WHERE f.id IS NULL ORDER BY g.id",
+            "Genus references missing family",
+        ),
+        (
+            FindingKind::OrphanedSpecimen,
+            "SELECT sp.id, sp.species_id AS parent_id FROM specimens sp \
+             LEFT JOIN species s ON sp.species_id = s.id WHERE s.id IS NULL ORDER BY sp.id",
+            "Specimen references missing species",
+        ),
+    ];
+
+    for (kind, sql, description) in orphan_checks {
+        let rows = sqlx::query(sql).fetch_all(pool).await?;
+        for row in rows {
+            let id_str: String = row.get("id");
+            let parent_str: String = row.get("parent_id");
+            findings.push(IntegrityFinding {
+                kind,
+                affected_id: parse_id(&id_str)?,
+                related_id: Uuid::parse_str(&parent_str).ok(),
+                message: format!("{} {}", description, parent_str),
+            });
+        }
+    }
+
+    let duplicate_checks = [
+        (FindingKind::DuplicateFamily, "families", "name", "", "Family"),
+        (FindingKind::DuplicateGenus, "genera", "name", "family_id, ", "Genus"),
+        (FindingKind::DuplicateSpecies, "species", "specific_epithet", "genus_id, ", "Species"),
+    ];
+
+    for (kind, table, name_column, parent, label) in duplicate_checks {
+        // One pass over the table: number each group of matching names in
+        // creation order and report everything after the first
+        let sql = format!(
+            "SELECT id, name, original_id FROM ( \
+                SELECT id, {name} AS name, \
+                    ROW_NUMBER() OVER duplicates AS rn, \
+                    FIRST_VALUE(id) OVER duplicates AS original_id \
+                FROM {table} \
+                WINDOW duplicates AS ( \
+                    PARTITION BY {parent}lower(trim({name})), lower(trim(coalesce(authority, ''))) \
+                    ORDER BY created_at, rowid \
+                ) \
+            ) WHERE rn > 1 ORDER BY id",
+            name = name_column,
+            table = table,
+            parent = parent,
+        );
+
+        let rows = sqlx::query(&sql).fetch_all(pool).await?;
+        for row in rows {
+            let id_str: String = row.get("id");
+            let name: String = row.get("name");
+            let original_str: String = row.get("original_id");
+            findings.push(IntegrityFinding {
+                kind,
+                affected_id: parse_id(&id_str)?,
+                related_id: Some(parse_id(&original_str)?),
+                message: format!("{} '{}' duplicates {}", label, name, original_str),
+            });
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Counts of changes made, or that would be made in a dry run, by `repair_integrity`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairSummary {
+    /// Whether the changes were rolled back instead of committed
+    pub dry_run: bool,
+
+    /// Orphaned species moved under the placeholder genus
+    pub species_reassigned: usize,
+
+    /// Orphaned genera moved under the placeholder family
+    pub genera_reassigned: usize,
+
+    /// Orphaned specimens deleted
+    pub specimens_deleted: usize,
+
+    /// Duplicate families, genera and species merged into their originals,
+    /// including genera merged to let their families merge
+    pub taxa_merged: usize,
+
+    /// Findings left alone, because they need `allow_deletes` or no longer apply
+    pub skipped: usize,
+}
+
+/// Fix the problems reported by `audit_integrity`
+///
+/// Orphaned species and genera are reassigned to an `Incertae sedis` placeholder
+/// genus and family, created on first use. Duplicate taxa are merged: their
+/// children are moved to the original record and the duplicate row is removed.
+/// When a duplicate family holds a genus with the same name and authority as a
+/// genus of the original family, that genus is merged into its twin first.
+/// Merging removes rows and orphaned specimens have nowhere to go, so both
+/// only happen when `allow_deletes` is set and are otherwise skipped.
+///
+/// Everything runs in one transaction, so a failure part-way rolls back every
+/// change. With `dry_run` the transaction is always rolled back and the summary
+/// reports what would have changed. Findings that no longer hold, e.g. an orphan
+/// whose parent has since been restored, are skipped.
+pub async fn repair_integrity(
+    pool: &SqlitePool,
+    findings: &[IntegrityFinding],
+    dry_run: bool,
+    allow_deletes: bool,
+) -> Result<RepairSummary, DatabaseError> {
+    let mut tx = pool.begin_with("BEGIN IMMEDIATE").await?;
+    let mut summary = RepairSummary { dry_run, ..RepairSummary::default() };
+    let mut placeholder_genus: Option<Uuid> = None;
+    let mut placeholder_family: Option<Uuid> = None;
+    let now = Utc::now();
+
+    for finding in findings {
+        let id = finding.affected_id.to_string();
+        let applied = match finding.kind {
+            FindingKind::OrphanedSpecies => {
+                let genus_id = match placeholder_genus {
+                    Some(genus_id) => genus_id,
+                    None => {
+                        let family_id = match placeholder_family {
+                            Some(family_id) => family_id,
+                            None => *placeholder_family.insert(placeholder_family_id(&mut tx).await?),
+                        };
+                        *placeholder_genus.insert(placeholder_genus_id(&mut tx, family_id).await?)
+                    }
+                };
+                let result = sqlx::query(
+                    "UPDATE species SET genus_id = ?, updated_at = ? \
+                     WHERE id = ? AND genus_id NOT IN (SELECT id FROM genera)"
+                )
+                .bind(genus_id.to_string())
+                .bind(now)
+                .bind(&id)
+                .execute(&mut *tx)
+                .await?;
+                summary.species_reassigned += result.rows_affected() as usize;
+                result.rows_affected() > 0
+            }
+            FindingKind::OrphanedGenus => {
+                let family_id = match placeholder_family {
+                    Some(family_id) => family_id,
+                    None => *placeholder_family.insert(placeholder_family_id(&mut tx).await?),
+                };
+                let result = sqlx::query(
+                    "UPDATE genera SET family_id = ?, updated_at = ? \
+                     WHERE id = ? AND family_id NOT IN (SELECT id FROM families)"
+                )
+                .bind(family_id.to_string())
+                .bind(now)
+                .bind(&id)
+                .execute(&mut *tx)
+                .await?;
+                summary.genera_reassigned += result.rows_affected() as usize;
+                result.rows_affected() > 0
+            }
+            FindingKind::OrphanedSpecimen => {
+                if allow_deletes {
+                    let result = sqlx::query(
+                        "DELETE FROM specimens WHERE id = ? AND species_id NOT IN (SELECT id FROM species)"
+                    )
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+                    summary.specimens_deleted += result.rows_affected() as usize;
+                    result.rows_affected() > 0
+                } else {
+                    false
+                }
+            }
+            FindingKind::DuplicateFamily | FindingKind::DuplicateGenus | FindingKind::DuplicateSpecies => {
+                match finding.related_id {
+                    Some(original_id) if allow_deletes => {
+                        let merged = merge_duplicate(&mut tx, finding.kind, finding.affected_id, original_id).await?;
+                        summary.taxa_merged += merged;
+                        merged > 0
+                    }
+                    _ => false,
+                }
+            }
+        };
+
+        if !applied {
+            summary.skipped += 1;
+        }
+    }
+
+    if dry_run {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+        log::info!("Integrity repair applied: {:?}", summary);
+    }
+
+    Ok(summary)
+}
+
+/// Move a duplicate's children to the original and delete the duplicate
+///
+/// Returns the number of taxa merged: zero if the finding no longer applies,
+/// otherwise one plus any genera merged to avoid name collisions.
+async fn merge_duplicate(
+    conn: &mut SqliteConnection,
+    kind: FindingKind,
+    duplicate_id: Uuid,
+    original_id: Uuid,
+) -> Result<usize, DatabaseError> {
+    let (table, child_table, child_column) = match kind {
+        FindingKind::DuplicateFamily => ("families", "genera", "family_id"),
+        FindingKind::DuplicateGenus => ("genera", "species", "genus_id"),
+        FindingKind::DuplicateSpecies => ("species", "specimens", "species_id"),
+        _ => return Ok(0),
+    };
+
+    let original_exists = sqlx::query(&format!("SELECT 1 FROM {} WHERE id = ?", table))
+        .bind(original_id.to_string())
+        .fetch_optional(&mut *conn)
+        .await?
+        .is_some();
+    if !original_exists || duplicate_id == original_id {
+        return Ok(0);
+    }
+
+    let mut merged = 0;
+    if kind == FindingKind::DuplicateFamily {
+        // Genera unique within a family would collide once moved, so fold each
+        // into its twin in the original family first
+        let collisions = sqlx::query(
+            "SELECT d.id AS duplicate_id, o.id AS original_id FROM genera d \
+             JOIN genera o ON o.family_id = ? AND o.name = d.name AND o.authority = d.authority \
+             WHERE d.family_id = ?"
+        )
+        .bind(original_id.to_string())
+        .bind(duplicate_id.to_string())
+        .fetch_all(&mut *conn)
+        .await?;
+
+        for row in collisions {
+            let duplicate_genus: String = row.get("duplicate_id");
+            let original_genus: String = row.get("original_id");
+            sqlx::query("UPDATE species SET genus_id = ? WHERE genus_id = ?")
+                .bind(&original_genus)
+                .bind(&duplicate_genus)
+                .execute(&mut *conn)
+                .await?;
+            sqlx::query("DELETE FROM genera WHERE id = ?")
+                .bind(&duplicate_genus)
+                .execute(&mut *conn)
+                .await?;
+            merged += 1;
+        }
+    }
+
+    sqlx::query(&format!("UPDATE {} SET {} = ? WHERE {} = ?", child_table, child_column, child_column))
+        .bind(original_id.to_string())
+        .bind(duplicate_id.to_string())
+        .execute(&mut *conn)
+        .await?;
+
+    if kind == FindingKind::DuplicateSpecies {
+        // Keep the original's value where both species set the same attribute
+        sqlx::query("UPDATE OR IGNORE species_attributes SET species_id = ? WHERE species_id = ?")
+            .bind(original_id.to_string())
+            .bind(duplicate_id.to_string())
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("UPDATE media SET species_id = ? WHERE species_id = ?")
+            .bind(original_id.to_string())
+            .bind(duplicate_id.to_string())
+            .execute(&mut *conn)
+            .await?;
+        // The duplicate's former names stay on record under the original
+        sqlx::query("UPDATE name_history SET species_id = ? WHERE species_id = ?")
+            .bind(original_id.to_string())
+            .bind(duplicate_id.to_string())
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("UPDATE annotations SET species_id = ? WHERE species_id = ?")
+            .bind(original_id.to_string())
+            .bind(duplicate_id.to_string())
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("UPDATE cultivation_events SET species_id = ? WHERE species_id = ?")
+            .bind(original_id.to_string())
+            .bind(duplicate_id.to_string())
+            .execute(&mut *conn)
+            .await?;
+        // Collections holding both keep their existing membership
+        sqlx::query("UPDATE OR IGNORE collection_members SET species_id = ? WHERE species_id = ?")
+            .bind(original_id.to_string())
+            .bind(duplicate_id.to_string())
+            .execute(&mut *conn)
+            .await?;
+        // Fold the duplicate's views into the original's
+        sqlx::query(
+            "INSERT INTO species_stats (species_id, view_count, last_viewed) \
+             SELECT ?, view_count, last_viewed FROM species_stats WHERE species_id = ? \
+             ON CONFLICT (species_id) DO UPDATE SET view_count = view_count + excluded.view_count, \
+             last_viewed = MAX(last_viewed, excluded.last_viewed)"
+        )
+        .bind(original_id.to_string())
+        .bind(duplicate_id.to_string())
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    let result = sqlx::query(&format!("DELETE FROM {} WHERE id = ?", table))
+        .bind(duplicate_id.to_string())
+        .execute(&mut *conn)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Ok(0);
+    }
+    Ok(merged + 1)
+}
+
+async fn placeholder_family_id(conn: &mut SqliteConnection) -> Result<Uuid, DatabaseError> {
+    let now = Utc::now();
+    sqlx::query(
+        "INSERT INTO families (id, name, authority, created_at, updated_at) VALUES (?, ?, '', ?, ?) \
+         ON CONFLICT (name, authority) DO NOTHING"
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(INCERTAE_SEDIS)
+    .bind(now)
+    .bind(now)
+    .execute(&mut *conn)
+    .await?;
+
+    let id_str: String = sqlx::query_scalar("SELECT id FROM families WHERE name = ? AND authority = ''")
+        .bind(INCERTAE_SEDIS)
+        .fetch_one(&mut *conn)
+        .await?;
+    parse_id(&id_str)
+}
+
+async fn placeholder_genus_id(conn: &mut SqliteConnection, family_id: Uuid) -> Result<Uuid, DatabaseError> {
+    let now = Utc::now();
+    sqlx::query(
+        "INSERT INTO genera (id, family_id, name, authority, created_at, updated_at) VALUES (?, ?, ?, '', ?, ?) \
+         ON CONFLICT (family_id, name, authority) DO NOTHING"
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(family_id.to_string())
+    .bind(INCERTAE_SEDIS)
+    .bind(now)
+    .bind(now)
+    .execute(&mut *conn)
+    .await?;
+
+    let id_str: String = sqlx::query_scalar("SELECT id FROM genera WHERE family_id = ? AND name = ? AND authority = ''")
+        .bind(family_id.to_string())
+        .bind(INCERTAE_SEDIS)
+        .fetch_one(&mut *conn)
+        .await?;
+    parse_id(&id_str)
+}
+
+fn parse_id(id: &str) -> Result<Uuid, DatabaseError> {
+    Uuid::parse_str(id).map_err(|e| DatabaseError::validation(e.to_string()))
+}
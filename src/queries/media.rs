@@ -0,0 +1,71 @@
+use sqlx::{SqlitePool, Row};
+use uuid::Uuid;
+use chrono::Utc;
+use crate::error::DatabaseError;
+use crate::types::Media;
+
+/// Attach a media item to a species
+///
+/// The URL and license must not be empty, so every stored item can be
+/// retrieved and attributed.
+pub async fn add_media(pool: &SqlitePool, media: &Media) -> Result<(), DatabaseError> {
+    if media.url.trim().is_empty() {
+        return Err(DatabaseError::validation("Media URL must not be empty"));
+    }
+    if media.license.trim().is_empty() {
+        return Err(DatabaseError::validation("Media license must not be empty"));
+    }
+    
+    sqlx::query(
+        "INSERT INTO media (id, species_id, url, media_type, license, creator, title, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(media.id.to_string())
+    .bind(media.species_id.to_string())
+    .bind(&media.url)
+    .bind(&media.media_type)
+    .bind(&media.license)
+    .bind(&media.creator)
+    .bind(&media.title)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+    
+    Ok(())
+}
+
+/// Get the media attached to a species, oldest first
+pub async fn get_media_for_species(pool: &SqlitePool, species_id: Uuid) -> Result<Vec<Media>, DatabaseError> {
+    let rows = sqlx::query(
+        "SELECT id, species_id, url, media_type, license, creator, title FROM media WHERE species_id = ? ORDER BY created_at, rowid"
+    )
+    .bind(species_id.to_string())
+    .fetch_all(pool)
+    .await?;
+    
+    let mut media = Vec::new();
+    for row in rows {
+        let id_str: String = row.get("id");
+        let species_id_str: String = row.get("species_id");
+        media.push(Media {
+            id: Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+            species_id: Uuid::parse_str(&species_id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+            url: row.get("url"),
+            media_type: row.get("media_type"),
+            license: row.get("license"),
+            creator: row.get("creator"),
+            title: row.get("title"),
+        });
+    }
+    
+    Ok(media)
+}
+
+/// Delete a media item
+pub async fn delete_media(pool: &SqlitePool, id: Uuid) -> Result<bool, DatabaseError> {
+    let result = sqlx::query("DELETE FROM media WHERE id = ?")
+        .bind(id.to_string())
+        .execute(pool)
+        .await?;
+    
+    Ok(result.rows_affected() > 0)
+}
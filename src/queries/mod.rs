@@ -2,4 +2,16 @@ pub mod species;
 pub mod genus;
 pub mod family;
 pub mod specimens;
-pub mod search;
\ No newline at end of file
+pub mod search;
+pub mod integrity;
+pub mod attributes;
+pub mod media;
+pub mod accessions;
+pub mod completeness;
+pub mod dataset;
+pub mod annotations;
+pub mod conservation;
+pub mod stats;
+pub mod cultivation;
+pub mod collections;
+pub mod context_documents;
\ No newline at end of file
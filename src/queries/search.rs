@@ -1,7 +1,320 @@
-use sqlx::SqlitePool;
+use std::fmt;
+use sqlx::{SqlitePool, Row};
+use uuid::Uuid;
 use crate::error::DatabaseError;
+use crate::queries::species::species_from_row;
+use crate::types::{Family, Genus, Species};
+
+/// A scientific name suggestion for autocomplete
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameSuggestion {
+    /// ID of the suggested species
+    pub species_id: Uuid,
+    
+    /// Full binomial, e.g. "Rosa canina"
+    pub scientific_name: String,
+    
+    /// Number of species stored in the suggestion's genus
+    pub genus_species_count: i64,
+}
 
 /// Stub implementation for search
 pub async fn search_species(_pool: &SqlitePool, _query: &str) -> Result<Vec<String>, DatabaseError> {
     Ok(Vec::new())
-}
\ No newline at end of file
+}
+
+/// Suggest scientific names starting with the given prefix
+///
+/// A single word matches the start of either the genus name or the specific
+/// epithet; "Genus epi" matches the genus exactly and the epithet by prefix.
+/// Matching is case-insensitive. Results favour larger genera, then sort
+/// alphabetically.
+pub async fn suggest_names(pool: &SqlitePool, prefix: &str, limit: usize) -> Result<Vec<NameSuggestion>, DatabaseError> {
+    suggest_names_with(pool, prefix, limit, false).await
+}
+
+/// Like `suggest_names`, optionally putting the most viewed species first
+///
+/// With `rank_by_views`, species are ordered by their recorded view count
+/// before genus size; unviewed species count as zero views.
+pub async fn suggest_names_with(
+    pool: &SqlitePool,
+    prefix: &str,
+    limit: usize,
+    rank_by_views: bool,
+) -> Result<Vec<NameSuggestion>, DatabaseError> {
+    let prefix = prefix.trim();
+    if prefix.is_empty() || limit == 0 {
+        return Ok(Vec::new());
+    }
+    
+    let (two_words, first, second) = match prefix.split_once(char::is_whitespace) {
+        Some((genus, epithet)) => (true, escape_like(genus), format!("{}%", escape_like(epithet.trim()))),
+        None => (false, format!("{}%", escape_like(prefix)), format!("{}%", escape_like(prefix))),
+    };
+    let sql = suggest_names_sql(two_words, rank_by_views);
+    
+    let rows = sqlx::query(&sql)
+        .bind(first)
+        .bind(second)
+        .bind(limit as i64)
+        .fetch_all(pool)
+        .await?;
+    
+    let mut suggestions = Vec::new();
+    for row in rows {
+        let id_str: String = row.get("id");
+        let genus_name: String = row.get("genus_name");
+        let specific_epithet: String = row.get("specific_epithet");
+        
+        suggestions.push(NameSuggestion {
+            species_id: Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+            scientific_name: format!("{} {}", genus_name, specific_epithet),
+            genus_species_count: row.get("genus_species_count"),
+        });
+    }
+    
+    Ok(suggestions)
+}
+
+/// SQL behind `suggest_names_with`
+///
+/// Each name filter is a prefix `LIKE` on its own branch so SQLite can serve
+/// it from the NOCASE name index, and genus sizes are counted only for the
+/// genera of matching species.
+pub(crate) fn suggest_names_sql(two_words: bool, rank_by_views: bool) -> String {
+    let matches = if two_words {
+        "SELECT s.id FROM species s JOIN genera g ON s.genus_id = g.id \
+         WHERE g.name LIKE ? ESCAPE '\\' AND s.specific_epithet LIKE ? ESCAPE '\\'"
+    } else {
+        "SELECT s.id FROM species s JOIN genera g ON s.genus_id = g.id WHERE g.name LIKE ? ESCAPE '\\' \
+         UNION \
+         SELECT s.id FROM species s WHERE s.specific_epithet LIKE ? ESCAPE '\\'"
+    };
+    let views = if rank_by_views {
+        "(SELECT COALESCE(MAX(st.view_count), 0) FROM species_stats st WHERE st.species_id = s.id) DESC, "
+    } else {
+        ""
+    };
+    
+    format!(
+        "WITH matches AS ({}), \
+         counts AS ( \
+            SELECT genus_id, COUNT(*) AS genus_species_count FROM species \
+            WHERE genus_id IN (SELECT s.genus_id FROM matches m JOIN species s ON s.id = m.id) \
+            GROUP BY genus_id \
+         ) \
+         SELECT s.id, g.name AS genus_name, s.specific_epithet, c.genus_species_count \
+         FROM matches m JOIN species s ON s.id = m.id \
+         JOIN genera g ON s.genus_id = g.id \
+         JOIN counts c ON c.genus_id = s.genus_id \
+         ORDER BY {}c.genus_species_count DESC, g.name COLLATE NOCASE, s.specific_epithet COLLATE NOCASE \
+         LIMIT ?",
+        matches, views
+    )
+}
+
+/// A taxonomic rank that search results can be scoped to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaxonRank {
+    Family,
+    Genus,
+    Species,
+}
+
+impl fmt::Display for TaxonRank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaxonRank::Family => write!(f, "Family"),
+            TaxonRank::Genus => write!(f, "Genus"),
+            TaxonRank::Species => write!(f, "Species"),
+        }
+    }
+}
+
+/// A taxon matched by `search_taxa_by_keyword`
+#[derive(Debug, Clone)]
+pub enum TaxonHit {
+    Family(Family),
+    Genus(Genus),
+    /// A species with the name of its genus, for display
+    Species { species: Species, genus_name: String },
+}
+
+impl TaxonHit {
+    /// Rank of the matched taxon
+    pub fn rank(&self) -> TaxonRank {
+        match self {
+            TaxonHit::Family(_) => TaxonRank::Family,
+            TaxonHit::Genus(_) => TaxonRank::Genus,
+            TaxonHit::Species { .. } => TaxonRank::Species,
+        }
+    }
+    
+    /// ID of the matched taxon
+    pub fn id(&self) -> Uuid {
+        match self {
+            TaxonHit::Family(family) => family.id,
+            TaxonHit::Genus(genus) => genus.id,
+            TaxonHit::Species { species, .. } => species.id,
+        }
+    }
+    
+    /// Name of the matched taxon; the binomial for species
+    pub fn name(&self) -> String {
+        match self {
+            TaxonHit::Family(family) => family.name.clone(),
+            TaxonHit::Genus(genus) => genus.name.clone(),
+            TaxonHit::Species { species, genus_name } => format!("{} {}", genus_name, species.specific_epithet),
+        }
+    }
+}
+
+/// Formats as `Rank: Name`, e.g. "Species: Rosa canina"
+impl fmt::Display for TaxonHit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.rank(), self.name())
+    }
+}
+
+/// Search families, genera and species whose name contains a keyword
+///
+/// Matching is case-insensitive; species match on their epithet or full
+/// binomial. `rank` restricts the search to one rank. Hits are ordered by rank,
+/// families first, then alphabetically.
+pub async fn search_taxa_by_keyword(
+    pool: &SqlitePool,
+    keyword: &str,
+    rank: Option<TaxonRank>,
+) -> Result<Vec<TaxonHit>, DatabaseError> {
+    let keyword = keyword.trim();
+    if keyword.is_empty() {
+        return Ok(Vec::new());
+    }
+    let pattern = format!("%{}%", escape_like(keyword));
+    let wanted = |r: TaxonRank| rank.is_none_or(|rank| rank == r);
+    let mut hits = Vec::new();
+    
+    if wanted(TaxonRank::Family) {
+        let rows = sqlx::query("SELECT id, name, authority FROM families WHERE name LIKE ? ESCAPE '\\' ORDER BY name COLLATE NOCASE, id")
+            .bind(&pattern)
+            .fetch_all(pool)
+            .await?;
+        for row in rows {
+            let id_str: String = row.get("id");
+            hits.push(TaxonHit::Family(Family::with_id(
+                Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+                row.get("name"),
+                row.get("authority"),
+            )));
+        }
+    }
+    
+    if wanted(TaxonRank::Genus) {
+        let rows = sqlx::query("SELECT id, family_id, name, authority FROM genera WHERE name LIKE ? ESCAPE '\\' ORDER BY name COLLATE NOCASE, id")
+            .bind(&pattern)
+            .fetch_all(pool)
+            .await?;
+        for row in rows {
+            let id_str: String = row.get("id");
+            let family_id_str: String = row.get("family_id");
+            hits.push(TaxonHit::Genus(Genus::with_id(
+                Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+                Uuid::parse_str(&family_id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+                row.get("name"),
+                row.get("authority"),
+            )));
+        }
+    }
+    
+    if wanted(TaxonRank::Species) {
+        let rows = sqlx::query(
+            "SELECT s.id, s.genus_id, s.specific_epithet, s.authority, s.publication_year, s.conservation_status, s.source, s.source_record_id, \
+             g.name AS genus_name \
+             FROM species s JOIN genera g ON s.genus_id = g.id \
+             WHERE s.specific_epithet LIKE ?1 ESCAPE '\\' OR g.name || ' ' || s.specific_epithet LIKE ?1 ESCAPE '\\' \
+             ORDER BY g.name COLLATE NOCASE, s.specific_epithet COLLATE NOCASE, s.id"
+        )
+        .bind(&pattern)
+        .fetch_all(pool)
+        .await?;
+        for row in rows {
+            hits.push(TaxonHit::Species {
+                species: species_from_row(&row)?,
+                genus_name: row.get("genus_name"),
+            });
+        }
+    }
+    
+    Ok(hits)
+}
+
+/// A species found by `search_species_resolving_synonyms`
+#[derive(Debug, Clone)]
+pub struct SpeciesMatch {
+    /// The accepted species
+    pub species: Species,
+    
+    /// Current binomial, e.g. "Rosa rubiginosa"
+    pub scientific_name: String,
+    
+    /// The former name that matched, when the current name did not
+    pub matched_synonym: Option<String>,
+}
+
+impl SpeciesMatch {
+    /// Note to show alongside the result, e.g. "matched via synonym: Rosa eglanteria L."
+    pub fn match_note(&self) -> Option<String> {
+        self.matched_synonym.as_ref().map(|name| format!("matched via synonym: {}", name))
+    }
+}
+
+/// Search species by current name or by any former name
+///
+/// Former names are those recorded in the name history when a species was
+/// renamed, so searching an outdated name still finds the accepted species.
+/// Matching is a case-insensitive substring match on the binomial, or on the
+/// full former name with its authority. Each species appears once: a match on
+/// the current name takes precedence, otherwise the most recent matching
+/// former name is reported. Current-name matches come first, then
+/// alphabetical order.
+pub async fn search_species_resolving_synonyms(pool: &SqlitePool, query: &str) -> Result<Vec<SpeciesMatch>, DatabaseError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    
+    let rows = sqlx::query(
+        "SELECT s.id, s.genus_id, s.specific_epithet, s.authority, s.publication_year, s.conservation_status, s.source, s.source_record_id, \
+             g.name AS genus_name, \
+             g.name || ' ' || s.specific_epithet LIKE ?1 ESCAPE '\\' AS direct, \
+             (SELECT h.old_name FROM name_history h \
+              WHERE h.species_id = s.id AND h.old_name LIKE ?1 ESCAPE '\\' \
+              ORDER BY h.changed_at DESC, h.id DESC LIMIT 1) AS synonym \
+         FROM species s JOIN genera g ON s.genus_id = g.id \
+         WHERE direct OR synonym IS NOT NULL \
+         ORDER BY direct DESC, g.name COLLATE NOCASE, s.specific_epithet COLLATE NOCASE, s.id"
+    )
+    .bind(format!("%{}%", escape_like(query)))
+    .fetch_all(pool)
+    .await?;
+    
+    let mut matches = Vec::new();
+    for row in rows {
+        let genus_name: String = row.get("genus_name");
+        let direct: bool = row.get("direct");
+        let species = species_from_row(&row)?;
+        matches.push(SpeciesMatch {
+            scientific_name: format!("{} {}", genus_name, species.specific_epithet),
+            matched_synonym: if direct { None } else { row.get("synonym") },
+            species,
+        });
+    }
+    
+    Ok(matches)
+}
+
+/// Escape `LIKE` wildcards so user input is matched literally
+pub(crate) fn escape_like(input: &str) -> String {
+    input.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
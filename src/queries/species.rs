@@ -1,12 +1,15 @@
-use sqlx::{SqlitePool, Row};
+use sqlx::{SqlitePool, SqliteConnection, Row};
+use sqlx::sqlite::SqliteRow;
 use uuid::Uuid;
+use chrono::{DateTime, Utc};
 use crate::error::DatabaseError;
-use crate::types::Species;
+use crate::types::{DataSource, Species};
 
 /// Insert a new species into the database
 pub async fn insert_species(pool: &SqlitePool, species: &Species) -> Result<(), DatabaseError> {
+    let now = Utc::now();
     sqlx::query(
-        "INSERT INTO species (id, genus_id, specific_epithet, authority, publication_year, conservation_status) VALUES (?, ?, ?, ?, ?, ?)"
+        "INSERT INTO species (id, genus_id, specific_epithet, authority, publication_year, conservation_status, source, source_record_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(species.id.to_string())
     .bind(species.genus_id.to_string())
@@ -14,6 +17,10 @@ pub async fn insert_species(pool: &SqlitePool, species: &Species) -> Result<(),
     .bind(&species.authority)
     .bind(species.publication_year)
     .bind(&species.conservation_status)
+    .bind(species.source.to_string())
+    .bind(&species.source_record_id)
+    .bind(now)
+    .bind(now)
     .execute(pool)
     .await?;
     
@@ -22,74 +29,279 @@ pub async fn insert_species(pool: &SqlitePool, species: &Species) -> Result<(),
 
 /// Get a species by ID
 pub async fn get_species_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Species>, DatabaseError> {
-    let row = sqlx::query("SELECT id, genus_id, specific_epithet, authority, publication_year, conservation_status FROM species WHERE id = ?")
+    let row = sqlx::query("SELECT id, genus_id, specific_epithet, authority, publication_year, conservation_status, source, source_record_id FROM species WHERE id = ?")
         .bind(id.to_string())
         .fetch_optional(pool)
         .await?;
     
-    if let Some(row) = row {
-        let id_str: String = row.get("id");
-        let genus_id_str: String = row.get("genus_id");
-        let specific_epithet: String = row.get("specific_epithet");
-        let authority: String = row.get("authority");
-        let publication_year: Option<i32> = row.get("publication_year");
-        let conservation_status: Option<String> = row.get("conservation_status");
-        
-        Ok(Some(Species::with_id(
-            Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
-            Uuid::parse_str(&genus_id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
-            specific_epithet,
-            authority,
-            publication_year,
-            conservation_status,
-        )))
-    } else {
-        Ok(None)
-    }
+    row.as_ref().map(species_from_row).transpose()
 }
 
 /// Get species by name pattern
 pub async fn get_species_by_name(pool: &SqlitePool, name: &str) -> Result<Vec<Species>, DatabaseError> {
-    let rows = sqlx::query("SELECT id, genus_id, specific_epithet, authority, publication_year, conservation_status FROM species WHERE specific_epithet LIKE ?")
+    let rows = sqlx::query("SELECT id, genus_id, specific_epithet, authority, publication_year, conservation_status, source, source_record_id FROM species WHERE specific_epithet LIKE ?")
         .bind(format!("%{}%", name))
         .fetch_all(pool)
         .await?;
     
-    let mut species = Vec::new();
-    for row in rows {
-        let id_str: String = row.get("id");
-        let genus_id_str: String = row.get("genus_id");
-        let specific_epithet: String = row.get("specific_epithet");
-        let authority: String = row.get("authority");
-        let publication_year: Option<i32> = row.get("publication_year");
-        let conservation_status: Option<String> = row.get("conservation_status");
-        
-        species.push(Species::with_id(
-            Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
-            Uuid::parse_str(&genus_id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
-            specific_epithet,
-            authority,
-            publication_year,
-            conservation_status,
-        ));
+    rows.iter().map(species_from_row).collect()
+}
+
+/// Get a species by its exact scientific name (genus + specific epithet)
+///
+/// Matching is case-insensitive. Returns `Ok(None)` when no such species is stored.
+pub async fn get_species_by_scientific_name(
+    pool: &SqlitePool,
+    genus: &str,
+    epithet: &str,
+) -> Result<Option<Species>, DatabaseError> {
+    let row = sqlx::query(
+        "SELECT s.id, s.genus_id, s.specific_epithet, s.authority, s.publication_year, s.conservation_status, s.source, s.source_record_id \
+         FROM species s JOIN genera g ON s.genus_id = g.id \
+         WHERE g.name = ? COLLATE NOCASE AND s.specific_epithet = ? COLLATE NOCASE \
+         LIMIT 1"
+    )
+    .bind(genus.trim())
+    .bind(epithet.trim())
+    .fetch_optional(pool)
+    .await?;
+    
+    row.as_ref().map(species_from_row).transpose()
+}
+
+/// A page of species from keyset pagination
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeciesPage {
+    /// Species on this page, ordered by ID
+    pub species: Vec<Species>,
+    
+    /// Cursor to pass as `after` for the next page; `None` on the last page
+    pub next_cursor: Option<Uuid>,
+}
+
+/// Get a page of species ordered by ID, starting after the given cursor
+///
+/// Uses keyset pagination (`WHERE id > after`) rather than `OFFSET`, so rows
+/// inserted or deleted while a client pages through are never skipped or repeated.
+pub async fn get_species_page(pool: &SqlitePool, after: Option<Uuid>, limit: usize) -> Result<SpeciesPage, DatabaseError> {
+    if limit == 0 {
+        return Err(DatabaseError::validation("Page limit must be greater than zero"));
     }
     
-    Ok(species)
+    // Fetch one extra row to learn whether another page follows
+    let rows = sqlx::query("SELECT id, genus_id, specific_epithet, authority, publication_year, conservation_status, source, source_record_id FROM species WHERE id > ? ORDER BY id LIMIT ?")
+        .bind(after.map(|id| id.to_string()).unwrap_or_default())
+        .bind(limit as i64 + 1)
+        .fetch_all(pool)
+        .await?;
+    
+    let mut species: Vec<Species> = rows.iter().map(species_from_row).collect::<Result<_, _>>()?;
+    let next_cursor = if species.len() > limit {
+        species.truncate(limit);
+        species.last().map(|s| s.id)
+    } else {
+        None
+    };
+    
+    Ok(SpeciesPage { species, next_cursor })
+}
+
+/// Get species created or updated after the given instant, oldest change first
+///
+/// Intended for incremental sync: pass the time of the previous pull to receive
+/// only the species that changed since then.
+pub async fn get_species_modified_since(pool: &SqlitePool, since: DateTime<Utc>) -> Result<Vec<Species>, DatabaseError> {
+    let rows = sqlx::query("SELECT id, genus_id, specific_epithet, authority, publication_year, conservation_status, source, source_record_id FROM species WHERE updated_at > ? ORDER BY updated_at, id")
+        .bind(since)
+        .fetch_all(pool)
+        .await?;
+    
+    rows.iter().map(species_from_row).collect()
+}
+
+/// Get species that came from the given source, ordered by source record ID
+///
+/// Useful for reviewing imported records separately from curated ones, or for
+/// re-syncing them against their source.
+pub async fn get_species_by_source(pool: &SqlitePool, source: &DataSource) -> Result<Vec<Species>, DatabaseError> {
+    let rows = sqlx::query("SELECT id, genus_id, specific_epithet, authority, publication_year, conservation_status, source, source_record_id FROM species WHERE source = ? ORDER BY source_record_id, id")
+        .bind(source.to_string())
+        .fetch_all(pool)
+        .await?;
+    
+    rows.iter().map(species_from_row).collect()
+}
+
+/// Get species related to the given one, for "see also" suggestions
+///
+/// Species in the same genus come first, then others in the same family.
+/// Within each tier, species sharing more attribute values with the target rank
+/// higher. The target itself is never included, and at most `limit` species are
+/// returned across both tiers.
+pub async fn get_related_species(pool: &SqlitePool, species_id: Uuid, limit: usize) -> Result<Vec<Species>, DatabaseError> {
+    get_related_species_with(pool, species_id, limit, false).await
+}
+
+/// Like `get_related_species`, optionally breaking ties by view count
+///
+/// With `rank_by_views`, species with equal attribute overlap are ordered by
+/// their recorded views, most viewed first.
+pub async fn get_related_species_with(
+    pool: &SqlitePool,
+    species_id: Uuid,
+    limit: usize,
+    rank_by_views: bool,
+) -> Result<Vec<Species>, DatabaseError> {
+    let views = if rank_by_views {
+        "(SELECT COALESCE(MAX(st.view_count), 0) FROM species_stats st WHERE st.species_id = s.id) DESC, "
+    } else {
+        ""
+    };
+    let sql = format!(
+        "SELECT s.id, s.genus_id, s.specific_epithet, s.authority, s.publication_year, s.conservation_status, s.source, s.source_record_id \
+         FROM species t \
+         JOIN genera tg ON tg.id = t.genus_id \
+         JOIN genera g ON g.family_id = tg.family_id \
+         JOIN species s ON s.genus_id = g.id \
+         WHERE t.id = ? AND s.id != t.id \
+         ORDER BY \
+             s.genus_id != t.genus_id, \
+             (SELECT COUNT(*) FROM species_attributes a \
+              JOIN species_attributes ta ON ta.species_id = t.id AND ta.key = a.key AND ta.value = a.value \
+              WHERE a.species_id = s.id) DESC, \
+             {}g.name, s.specific_epithet, s.id \
+         LIMIT ?",
+        views
+    );
+    let rows = sqlx::query(&sql)
+        .bind(species_id.to_string())
+        .bind(limit as i64)
+        .fetch_all(pool)
+        .await?;
+    
+    rows.iter().map(species_from_row).collect()
 }
 
 /// Update a species
+///
+/// A change of genus, epithet or authority is recorded in the name history;
+/// use `update_species_with_reason` to say why.
 pub async fn update_species(pool: &SqlitePool, id: Uuid, species: &Species) -> Result<bool, DatabaseError> {
-    let result = sqlx::query("UPDATE species SET genus_id = ?, specific_epithet = ?, authority = ?, publication_year = ?, conservation_status = ? WHERE id = ?")
+    update_species_with_reason(pool, id, species, None).await
+}
+
+/// Update a species, recording any name change in the name history with a reason
+pub async fn update_species_with_reason(
+    pool: &SqlitePool,
+    id: Uuid,
+    species: &Species,
+    reason: Option<&str>,
+) -> Result<bool, DatabaseError> {
+    let mut tx = pool.begin_with("BEGIN IMMEDIATE").await?;
+    let old_name = scientific_name_of(&mut tx, id).await?;
+    
+    let result = sqlx::query("UPDATE species SET genus_id = ?, specific_epithet = ?, authority = ?, publication_year = ?, conservation_status = ?, source = ?, source_record_id = ?, updated_at = ? WHERE id = ?")
         .bind(species.genus_id.to_string())
         .bind(&species.specific_epithet)
         .bind(&species.authority)
         .bind(species.publication_year)
         .bind(&species.conservation_status)
+        .bind(species.source.to_string())
+        .bind(&species.source_record_id)
+        .bind(Utc::now())
         .bind(id.to_string())
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     
-    Ok(result.rows_affected() > 0)
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+    
+    let new_name = scientific_name_of(&mut tx, id).await?;
+    if let (Some(old_name), Some(new_name)) = (old_name, new_name) {
+        if old_name != new_name {
+            record_name_change(&mut tx, id, &old_name, &new_name, reason).await?;
+        }
+    }
+    
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// A recorded change to a species' scientific name
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameChange {
+    /// The species that was renamed
+    pub species_id: Uuid,
+    
+    /// Full name with authority before the change, e.g. `Rosa eglanteria L.`
+    pub old_name: String,
+    
+    /// Full name with authority after the change
+    pub new_name: String,
+    
+    /// When the change was made
+    pub changed_at: DateTime<Utc>,
+    
+    /// Why the name changed, if given
+    pub reason: Option<String>,
+}
+
+/// Get the name changes of a species, oldest first
+pub async fn get_name_history(pool: &SqlitePool, species_id: Uuid) -> Result<Vec<NameChange>, DatabaseError> {
+    let rows = sqlx::query("SELECT old_name, new_name, changed_at, reason FROM name_history WHERE species_id = ? ORDER BY changed_at, id")
+        .bind(species_id.to_string())
+        .fetch_all(pool)
+        .await?;
+    
+    Ok(rows.iter().map(|row| NameChange {
+        species_id,
+        old_name: row.get("old_name"),
+        new_name: row.get("new_name"),
+        changed_at: row.get("changed_at"),
+        reason: row.get("reason"),
+    }).collect())
+}
+
+/// Full scientific name with authority of a stored species
+pub(crate) async fn scientific_name_of(conn: &mut SqliteConnection, id: Uuid) -> Result<Option<String>, DatabaseError> {
+    let row = sqlx::query(
+        "SELECT COALESCE(g.name, s.genus_id) AS genus, s.specific_epithet, s.authority \
+         FROM species s LEFT JOIN genera g ON s.genus_id = g.id WHERE s.id = ?"
+    )
+    .bind(id.to_string())
+    .fetch_optional(&mut *conn)
+    .await?;
+    
+    Ok(row.map(|row| {
+        let genus: String = row.get("genus");
+        let epithet: String = row.get("specific_epithet");
+        let authority: Option<String> = row.get("authority");
+        match authority.filter(|a| !a.trim().is_empty()) {
+            Some(authority) => format!("{} {} {}", genus, epithet, authority),
+            None => format!("{} {}", genus, epithet),
+        }
+    }))
+}
+
+/// Append an entry to the name history of a species
+pub(crate) async fn record_name_change(
+    conn: &mut SqliteConnection,
+    species_id: Uuid,
+    old_name: &str,
+    new_name: &str,
+    reason: Option<&str>,
+) -> Result<(), DatabaseError> {
+    sqlx::query("INSERT INTO name_history (species_id, old_name, new_name, changed_at, reason) VALUES (?, ?, ?, ?, ?)")
+        .bind(species_id.to_string())
+        .bind(old_name)
+        .bind(new_name)
+        .bind(Utc::now())
+        .bind(reason)
+        .execute(&mut *conn)
+        .await?;
+    
+    Ok(())
 }
 
 /// Delete a species
@@ -100,4 +312,133 @@ pub async fn delete_species(pool: &SqlitePool, id: Uuid) -> Result<bool, Databas
         .await?;
     
     Ok(result.rows_affected() > 0)
+}
+
+/// Rows removed by `delete_species_batch`, per table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteSummary {
+    /// Species rows deleted
+    pub species: usize,
+    
+    /// Specimens deleted along with their species
+    pub specimens: usize,
+    
+    /// Species attributes deleted
+    pub attributes: usize,
+    
+    /// Media items deleted
+    pub media: usize,
+    
+    /// Name history entries deleted
+    pub name_history: usize,
+    
+    /// Annotations deleted
+    pub annotations: usize,
+    
+    /// Cultivation events deleted
+    pub cultivation_events: usize,
+    
+    /// View statistics rows deleted
+    pub stats: usize,
+    
+    /// Collection memberships removed
+    pub collection_members: usize,
+}
+
+/// Maximum IDs bound into one `IN (...)` list, well under SQLite's variable limit
+const DELETE_BATCH_CHUNK: usize = 500;
+
+/// Delete many species in one transaction
+///
+/// Specimens block deletion: without `cascade` the call fails with a
+/// `ConstraintViolation` listing every species that still has specimens and
+/// deletes nothing. With `cascade` those specimens are deleted too. Attributes,
+/// media, name history, annotations, cultivation events, view statistics and
+/// collection memberships always go with their species, through the schema's
+/// `ON DELETE CASCADE`. IDs that do not exist are ignored.
+pub async fn delete_species_batch(pool: &SqlitePool, ids: &[Uuid], cascade: bool) -> Result<DeleteSummary, DatabaseError> {
+    let mut tx = pool.begin_with("BEGIN IMMEDIATE").await?;
+    let mut summary = DeleteSummary::default();
+    
+    if !cascade {
+        let mut blocked: Vec<String> = Vec::new();
+        for chunk in ids.chunks(DELETE_BATCH_CHUNK) {
+            let sql = format!("SELECT DISTINCT species_id FROM specimens WHERE species_id IN ({}) ORDER BY species_id", placeholders(chunk.len()));
+            let mut query = sqlx::query_scalar::<_, String>(&sql);
+            for id in chunk {
+                query = query.bind(id.to_string());
+            }
+            blocked.extend(query.fetch_all(&mut *tx).await?);
+        }
+        if !blocked.is_empty() {
+            return Err(DatabaseError::constraint(format!(
+                "Species have dependent specimens: {}", blocked.join(", ")
+            )));
+        }
+    }
+    
+    for chunk in ids.chunks(DELETE_BATCH_CHUNK) {
+        let list = placeholders(chunk.len());
+        
+        // Every table but specimens cascades from species, so count their rows
+        // while they still exist
+        let cascaded = [
+            ("species_attributes", &mut summary.attributes),
+            ("media", &mut summary.media),
+            ("name_history", &mut summary.name_history),
+            ("annotations", &mut summary.annotations),
+            ("cultivation_events", &mut summary.cultivation_events),
+            ("species_stats", &mut summary.stats),
+            ("collection_members", &mut summary.collection_members),
+        ];
+        for (table, count) in cascaded {
+            let sql = format!("SELECT COUNT(*) FROM {} WHERE species_id IN ({})", table, list);
+            let mut query = sqlx::query_scalar::<_, i64>(&sql);
+            for id in chunk {
+                query = query.bind(id.to_string());
+            }
+            *count += query.fetch_one(&mut *tx).await? as usize;
+        }
+        
+        let deletes = [
+            (format!("DELETE FROM specimens WHERE species_id IN ({})", list), &mut summary.specimens),
+            (format!("DELETE FROM species WHERE id IN ({})", list), &mut summary.species),
+        ];
+        for (sql, count) in deletes {
+            let mut query = sqlx::query(&sql);
+            for id in chunk {
+                query = query.bind(id.to_string());
+            }
+            *count += query.execute(&mut *tx).await?.rows_affected() as usize;
+        }
+    }
+    
+    tx.commit().await?;
+    Ok(summary)
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+/// Build a species from a row selecting the standard species columns
+pub(crate) fn species_from_row(row: &SqliteRow) -> Result<Species, DatabaseError> {
+    let id_str: String = row.get("id");
+    let genus_id_str: String = row.get("genus_id");
+    let specific_epithet: String = row.get("specific_epithet");
+    let authority: String = row.get("authority");
+    let publication_year: Option<i32> = row.get("publication_year");
+    let conservation_status: Option<String> = row.get("conservation_status");
+    let source: String = row.get("source");
+    let source_record_id: Option<String> = row.get("source_record_id");
+    
+    Ok(Species::with_id(
+        Uuid::parse_str(&id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+        Uuid::parse_str(&genus_id_str).map_err(|e| DatabaseError::validation(e.to_string()))?,
+        specific_epithet,
+        authority,
+        publication_year,
+        conservation_status,
+    )
+    .with_source(source.parse()?, source_record_id))
 }
\ No newline at end of file
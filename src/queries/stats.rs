@@ -0,0 +1,82 @@
+use sqlx::{SqlitePool, Row};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::error::DatabaseError;
+use crate::types::Species;
+use super::species::species_from_row;
+
+/// How often a species has been viewed
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeciesStats {
+    /// The viewed species
+    pub species_id: Uuid,
+    
+    /// Number of recorded views
+    pub view_count: i64,
+    
+    /// When the species was last viewed
+    pub last_viewed: DateTime<Utc>,
+}
+
+/// Count one view of a species
+///
+/// A single upsert on the species' own stats row, so concurrent views never
+/// contend on more than that row. Fails with a `ForeignKeyViolation` if the
+/// species does not exist.
+pub async fn record_species_view(pool: &SqlitePool, species_id: Uuid) -> Result<(), DatabaseError> {
+    sqlx::query(
+        "INSERT INTO species_stats (species_id, view_count, last_viewed) VALUES (?, 1, ?) \
+         ON CONFLICT (species_id) DO UPDATE SET view_count = view_count + 1, last_viewed = excluded.last_viewed"
+    )
+    .bind(species_id.to_string())
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+    
+    Ok(())
+}
+
+/// Count one view of a species without waiting for the write
+///
+/// Spawns the update on the Tokio runtime so a read path can return straight
+/// away; failures are logged rather than returned. Must be called from within
+/// a runtime.
+pub fn record_species_view_detached(pool: &SqlitePool, species_id: Uuid) {
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        if let Err(e) = record_species_view(&pool, species_id).await {
+            log::warn!("Failed to record view of species {}: {}", species_id, e);
+        }
+    });
+}
+
+/// Get the view statistics of a species, or `None` if it has never been viewed
+pub async fn get_species_stats(pool: &SqlitePool, species_id: Uuid) -> Result<Option<SpeciesStats>, DatabaseError> {
+    let row = sqlx::query("SELECT view_count, last_viewed FROM species_stats WHERE species_id = ?")
+        .bind(species_id.to_string())
+        .fetch_optional(pool)
+        .await?;
+    
+    Ok(row.map(|row| SpeciesStats {
+        species_id,
+        view_count: row.get("view_count"),
+        last_viewed: row.get("last_viewed"),
+    }))
+}
+
+/// Get the most viewed species, most views first
+///
+/// Species that have never been viewed are not included.
+pub async fn get_most_viewed_species(pool: &SqlitePool, limit: usize) -> Result<Vec<Species>, DatabaseError> {
+    let rows = sqlx::query(
+        "SELECT s.id, s.genus_id, s.specific_epithet, s.authority, s.publication_year, s.conservation_status, s.source, s.source_record_id \
+         FROM species_stats st JOIN species s ON s.id = st.species_id \
+         ORDER BY st.view_count DESC, st.last_viewed DESC, s.id \
+         LIMIT ?"
+    )
+    .bind(limit as i64)
+    .fetch_all(pool)
+    .await?;
+    
+    rows.iter().map(species_from_row).collect()
+}
@@ -0,0 +1,64 @@
+//! Accession number tests
+//!
+//! Tests accession number formatting, parsing and race-safe allocation.
+
+use std::collections::HashSet;
+use super::setup_test_database;
+use crate::database::{BotanicalDatabase, DatabaseConfig};
+use crate::queries::accessions::next_accession_number;
+use crate::types::AccessionNumber;
+
+#[test]
+fn test_accession_number_format_and_parse() {
+    let accession = AccessionNumber::new(2024, 137);
+    assert_eq!(accession.to_string(), "2024-0137");
+    
+    let division = accession.with_qualifier("A").expect("Valid qualifier");
+    assert_eq!(division.to_string(), "2024-0137*A");
+    assert_eq!(division.base(), accession);
+    assert_eq!("2024-0137*A".parse::<AccessionNumber>().unwrap(), division);
+    assert_eq!("2024-12345".parse::<AccessionNumber>().unwrap(), AccessionNumber::new(2024, 12345));
+    
+    for text in ["2024-137", "24-0137", "2024-0137*a", "2024-0137*", "2024/0137", "2024-01x7"] {
+        assert!(text.parse::<AccessionNumber>().is_err(), "'{}' should be rejected", text);
+    }
+}
+
+#[tokio::test]
+async fn test_next_accession_number_counts_per_year() {
+    let db = setup_test_database().await;
+    
+    let first = next_accession_number(db.pool(), 2024).await.expect("Failed to allocate");
+    let second = next_accession_number(db.pool(), 2024).await.expect("Failed to allocate");
+    let other_year = next_accession_number(db.pool(), 2025).await.expect("Failed to allocate");
+    
+    assert_eq!(first.to_string(), "2024-0001");
+    assert_eq!(second.to_string(), "2024-0002");
+    assert_eq!(other_year.to_string(), "2025-0001");
+    assert!(next_accession_number(db.pool(), 12024).await.is_err());
+}
+
+#[tokio::test]
+async fn test_next_accession_number_is_race_safe() {
+    let path = std::env::temp_dir().join(format!("botanica-accessions-{}.db", uuid::Uuid::new_v4()));
+    let config = DatabaseConfig { url: format!("sqlite:{}?mode=rwc", path.display()), ..DatabaseConfig::default() };
+    let db = BotanicalDatabase::new(config).await.expect("Failed to create database");
+    db.migrate().await.expect("Failed to migrate");
+    
+    let tasks: Vec<_> = (0..20)
+        .map(|_| {
+            let pool = db.pool().clone();
+            tokio::spawn(async move { next_accession_number(&pool, 2024).await })
+        })
+        .collect();
+    
+    let mut sequences = HashSet::new();
+    for task in tasks {
+        let accession = task.await.expect("Task panicked").expect("Failed to allocate");
+        assert!(sequences.insert(accession.sequence), "Duplicate accession {}", accession);
+    }
+    assert_eq!(sequences, (1..=20).collect::<HashSet<u32>>());
+    
+    db.close().await;
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,48 @@
+//! Care schedule tests
+//!
+//! Tests the built-in growth-stage care actions and custom schedules.
+
+use crate::types::{CareAction, CareSchedule, GrowthStage, care_schedule};
+
+const ALL_STAGES: [GrowthStage; 8] = [
+    GrowthStage::Seed,
+    GrowthStage::Germination,
+    GrowthStage::Seedling,
+    GrowthStage::Vegetative,
+    GrowthStage::Flowering,
+    GrowthStage::Harvest,
+    GrowthStage::Drying,
+    GrowthStage::Curing,
+];
+
+#[test]
+fn test_builtin_schedule_covers_every_stage() {
+    for stage in ALL_STAGES {
+        // Exhaustive match: adding a GrowthStage variant without updating ALL_STAGES fails to compile
+        match stage {
+            GrowthStage::Seed | GrowthStage::Germination | GrowthStage::Seedling | GrowthStage::Vegetative
+            | GrowthStage::Flowering | GrowthStage::Harvest | GrowthStage::Drying | GrowthStage::Curing => {}
+        }
+        assert!(!care_schedule(stage.clone()).is_empty(), "{:?} has no care actions", stage);
+    }
+}
+
+#[test]
+fn test_flowering_reduces_nitrogen() {
+    let actions = care_schedule(GrowthStage::Flowering);
+    let nitrogen = actions.iter().find(|a| a.action.contains("nitrogen")).expect("Flowering should adjust nitrogen");
+    assert_eq!(nitrogen.interval_days, Some(14));
+    assert!(care_schedule(GrowthStage::Vegetative).iter().any(|a| a.action.contains("nitrogen-rich")));
+}
+
+#[test]
+fn test_custom_schedule() {
+    let schedule = CareSchedule::empty()
+        .with_action(GrowthStage::Seedling, CareAction::new("Mist leaves", Some(1)))
+        .with_action(GrowthStage::Seedling, CareAction::new("Pot on", None));
+    
+    let actions = schedule.actions_for(&GrowthStage::Seedling);
+    assert_eq!(actions.len(), 2);
+    assert_eq!(actions[1], CareAction::new("Pot on", None));
+    assert!(schedule.actions_for(&GrowthStage::Curing).is_empty());
+}
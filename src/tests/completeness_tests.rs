@@ -0,0 +1,62 @@
+//! Taxonomic completeness tests
+//!
+//! Tests per-record completeness scoring and the per-family report.
+
+use super::{setup_test_database, setup_sample_taxonomy, create_test_family, create_test_genus, create_test_species};
+use crate::queries::completeness::*;
+use crate::queries::species::insert_species;
+use crate::types::{Family, Species};
+
+#[test]
+fn test_complete_record_scores_full_marks() {
+    let family = create_test_family();
+    let genus = create_test_genus(family.id);
+    let species = create_test_species(genus.id);
+    
+    let score = taxonomic_completeness(&species, Lineage { genus: Some(&genus), family: Some(&family) });
+    assert_eq!(score.score, 100.0);
+    assert!(score.missing.is_empty());
+}
+
+#[test]
+fn test_missing_components_reduce_score() {
+    let family = create_test_family();
+    let genus = create_test_genus(family.id);
+    let sparse = Species::new(genus.id, "canina".to_string(), "".to_string(), None, None);
+    
+    let score = taxonomic_completeness(&sparse, Lineage { genus: Some(&genus), family: None });
+    assert_eq!(score.score, 12.5, "Only half the lineage component is present");
+    assert_eq!(score.missing, vec!["authority", "publication_year", "lineage", "conservation_status"]);
+    
+    // A family that does not match the genus does not count
+    let other_family = Family::new("Fagaceae".to_string(), "Dumort.".to_string());
+    let score = taxonomic_completeness(&sparse, Lineage { genus: Some(&genus), family: Some(&other_family) });
+    assert_eq!(score.score, 12.5);
+}
+
+#[test]
+fn test_custom_weights() {
+    let family = create_test_family();
+    let genus = create_test_genus(family.id);
+    let species = Species::new(genus.id, "canina".to_string(), "L.".to_string(), None, None);
+    let weights = CompletenessWeights { authority: 3, publication_year: 1, lineage: 0, conservation_status: 0 };
+    
+    let score = taxonomic_completeness_with(&species, Lineage::default(), &weights);
+    assert_eq!(score.score, 75.0);
+    assert_eq!(score.missing, vec!["publication_year"], "Zero-weight components are not reported");
+}
+
+#[tokio::test]
+async fn test_completeness_report_averages_per_family() {
+    let db = setup_test_database().await;
+    let (family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let sparse = Species::new(genus.id, "canina".to_string(), "L.".to_string(), None, None);
+    insert_species(db.pool(), &sparse).await.expect("Failed to insert species");
+    
+    let report = completeness_report(db.pool()).await.expect("Failed to build report");
+    assert_eq!(report.len(), 1);
+    assert_eq!(report[0].family_id, Some(family.id));
+    assert_eq!(report[0].family_name.as_deref(), Some("Rosaceae"));
+    assert_eq!(report[0].species_count, 2);
+    assert_eq!(report[0].average_score, 75.0);
+}
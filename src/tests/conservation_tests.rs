@@ -0,0 +1,119 @@
+//! Conservation model tests
+//!
+//! Tests IUCN criteria and category parsing, and the conservation breakdown query.
+
+use crate::types::conservation::*;
+use crate::DatabaseError;
+
+#[test]
+fn test_parse_simple_criterion() {
+    let criteria = parse_iucn_criteria("A2acd").expect("Failed to parse criteria");
+    
+    assert_eq!(criteria.len(), 1);
+    assert_eq!(criteria[0].letter, 'A');
+    assert_eq!(criteria[0].subcriteria.len(), 1);
+    assert_eq!(criteria[0].subcriteria[0].number, 2);
+    let letters: Vec<char> = criteria[0].subcriteria[0].qualifiers.iter().map(|q| q.letter).collect();
+    assert_eq!(letters, vec!['a', 'c', 'd']);
+}
+
+#[test]
+fn test_parse_compound_criteria_with_details() {
+    let criteria = parse_iucn_criteria("B1ab(iii,v)+2ab(iii); C2a(i); D; E").expect("Failed to parse criteria");
+    
+    assert_eq!(criteria.len(), 4);
+    assert_eq!(criteria[0].subcriteria.len(), 2);
+    assert_eq!(criteria[0].subcriteria[0].qualifiers[1].details, vec!["iii", "v"]);
+    assert!(criteria[2].subcriteria.is_empty(), "D may stand alone");
+    assert!(criteria[3].subcriteria.is_empty(), "E takes no subcriteria");
+    
+    let rendered: Vec<String> = criteria.iter().map(|c| c.to_string()).collect();
+    assert_eq!(rendered, vec!["B1ab(iii,v)+2ab(iii)", "C2a(i)", "D", "E"]);
+}
+
+#[test]
+fn test_parse_rejects_unknown_letter() {
+    let result = parse_iucn_criteria("A2a; Z9");
+    
+    match result {
+        Err(DatabaseError::ValidationError(msg)) => assert!(msg.contains("'Z9'"), "Error should name the bad token: {}", msg),
+        other => panic!("Expected validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_rejects_missing_subcriterion() {
+    let result = parse_iucn_criteria("A");
+    
+    match result {
+        Err(DatabaseError::ValidationError(msg)) => assert!(msg.contains("'A'"), "Error should name the bad token: {}", msg),
+        other => panic!("Expected validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_rejects_malformed_subcriteria() {
+    for bad in ["A5a", "B1ab(vi)", "C2A", "E1", "B1a(", "A2(i)", ""] {
+        assert!(parse_iucn_criteria(bad).is_err(), "'{}' should be rejected", bad);
+    }
+}
+
+#[test]
+fn test_parse_rejects_missing_qualifiers() {
+    for bad in ["A2", "A4", "B1", "B2a", "B1ab", "C2", "C2a", "B1ab(iii)+2", "A3a", "C1a", "D1a"] {
+        match parse_iucn_criteria(bad) {
+            Err(DatabaseError::ValidationError(msg)) => assert!(msg.contains(&format!("'{}'", bad)), "Error should name the bad token: {}", msg),
+            other => panic!("'{}' should be rejected, got {:?}", bad, other),
+        }
+    }
+    for good in ["A2c", "A3bc", "B1ab(iii)", "B2b(ii)c(iv)", "C1", "C2a(i)", "C2b", "D1", "D2"] {
+        assert!(parse_iucn_criteria(good).is_ok(), "'{}' should be accepted", good);
+    }
+}
+
+#[test]
+fn test_iucn_category_codes_round_trip() {
+    for category in IUCNCategory::ALL {
+        assert_eq!(category.code().parse::<IUCNCategory>().unwrap(), category);
+    }
+    assert_eq!(" vu ".parse::<IUCNCategory>().unwrap(), IUCNCategory::Vulnerable);
+    assert!(IUCNCategory::Endangered.is_threatened());
+    assert!(!IUCNCategory::NearThreatened.is_threatened());
+    assert!(matches!("XX".parse::<IUCNCategory>(), Err(DatabaseError::ValidationError(_))));
+}
+
+#[tokio::test]
+async fn test_conservation_breakdown() {
+    use super::{setup_test_database, setup_sample_taxonomy};
+    use crate::queries::conservation::*;
+    use crate::queries::species::insert_species;
+    use crate::types::Species;
+    
+    let db = setup_test_database().await;
+    assert!(conservation_breakdown(db.pool()).await.expect("Failed to count").is_empty());
+    
+    let (_, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    for (epithet, status) in [("canina", Some("EN")), ("gallica", Some("en")), ("arvensis", None), ("moschata", Some("NE")), ("spinosissima", Some("unknown"))] {
+        let species = Species::new(genus.id, epithet.to_string(), "L.".to_string(), None, status.map(str::to_string));
+        insert_species(db.pool(), &species).await.expect("Failed to insert species");
+    }
+    
+    let breakdown = conservation_breakdown(db.pool()).await.expect("Failed to count");
+    assert_eq!(breakdown.len(), 3);
+    assert_eq!(breakdown[&IUCNCategory::LeastConcern], 1);
+    assert_eq!(breakdown[&IUCNCategory::Endangered], 2);
+    assert_eq!(breakdown[&IUCNCategory::NotEvaluated], 3);
+    
+    let percentages = breakdown_percentages(&breakdown);
+    assert!((percentages[&IUCNCategory::NotEvaluated] - 50.0).abs() < 1e-9);
+    assert!((percentages.values().sum::<f64>() - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_iucn_category_localized_names() {
+    assert_eq!(IUCNCategory::CriticallyEndangered.localized_name("fr"), "En danger critique");
+    assert_eq!(IUCNCategory::LeastConcern.localized_name("es-MX"), "Preocupación Menor");
+    assert_eq!(IUCNCategory::ExtinctInTheWild.localized_name("EN"), "Extinct in the Wild");
+    assert_eq!(IUCNCategory::Vulnerable.localized_name("de"), "Vulnerable", "Unknown languages fall back to English");
+    assert_eq!(IUCNCategory::CriticallyEndangered.to_string(), "CR");
+}
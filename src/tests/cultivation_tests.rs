@@ -0,0 +1,111 @@
+//! Cultivation record tests
+//!
+//! Tests validated construction of cultivation records and environment readings,
+//! and storage of cultivation events.
+
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+use crate::types::{CultivationRecord, Environment, GrowthStage};
+
+#[test]
+fn test_builder_sets_fields() {
+    let species_id = Uuid::new_v4();
+    let mut environment = Environment::new();
+    environment.humidity_percent = Some(55.0);
+    environment.ph_level = Some(6.2);
+    let recorded_at = Utc.with_ymd_and_hms(2024, 5, 1, 9, 30, 0).unwrap();
+    
+    let record = CultivationRecord::builder(species_id, GrowthStage::Vegetative)
+        .cultivator(" grower ")
+        .notes("Topped main stem")
+        .photo("img/0001.jpg")
+        .environment(&environment)
+        .recorded_at(recorded_at)
+        .build()
+        .expect("Valid record should build");
+    
+    assert_eq!(record.species_id, species_id);
+    assert_eq!(record.growth_stage, GrowthStage::Vegetative);
+    assert_eq!(record.cultivator, "grower");
+    assert_eq!(record.notes.as_deref(), Some("Topped main stem"));
+    assert_eq!(record.photos, vec!["img/0001.jpg".to_string()]);
+    assert_eq!(record.environment_id, Some(environment.id));
+    assert_eq!(record.recorded_at, recorded_at);
+}
+
+#[test]
+fn test_builder_timestamps_record() {
+    let before = Utc::now();
+    let record = CultivationRecord::builder(Uuid::new_v4(), GrowthStage::Seedling)
+        .cultivator("grower")
+        .build()
+        .expect("Valid record should build");
+    assert!(record.recorded_at >= before);
+    assert!(record.notes.is_none());
+}
+
+#[test]
+fn test_builder_rejects_blank_cultivator() {
+    let result = CultivationRecord::builder(Uuid::new_v4(), GrowthStage::Seed).cultivator("   ").build();
+    assert!(matches!(result, Err(crate::DatabaseError::ValidationError(_))));
+    assert!(CultivationRecord::builder(Uuid::new_v4(), GrowthStage::Seed).build().is_err());
+}
+
+#[test]
+fn test_builder_rejects_out_of_range_environment() {
+    let mut environment = Environment::new();
+    environment.humidity_percent = Some(140.0);
+    let result = CultivationRecord::builder(Uuid::new_v4(), GrowthStage::Flowering)
+        .cultivator("grower")
+        .environment(&environment)
+        .build();
+    let err = result.expect_err("Humidity over 100% should be rejected");
+    assert!(err.to_string().contains("humidity_percent"));
+    
+    environment.humidity_percent = Some(60.0);
+    environment.ph_level = Some(f32::NAN);
+    assert!(environment.validate().is_err());
+    environment.ph_level = None;
+    environment.co2_ppm = Some(-1);
+    assert!(environment.validate().is_err());
+    environment.co2_ppm = Some(800);
+    assert!(environment.validate().is_ok());
+}
+
+#[test]
+fn test_event_type_text_round_trip() {
+    use crate::types::EventType;
+    
+    for event_type in [EventType::Watering, EventType::PestTreatment, EventType::Custom("defoliation".to_string())] {
+        assert_eq!(event_type.to_string().parse::<EventType>().unwrap(), event_type);
+    }
+    assert_eq!(EventType::PestTreatment.to_string(), "pest_treatment");
+    assert!("custom:".parse::<EventType>().is_err());
+    assert!("misting".parse::<EventType>().is_err());
+}
+
+#[tokio::test]
+async fn test_cultivation_events_by_range_and_recency() {
+    use super::{setup_test_database, setup_sample_taxonomy};
+    use crate::queries::cultivation::*;
+    use crate::types::{CultivationEvent, EventType};
+    
+    let db = setup_test_database().await;
+    let (_, _, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let day = |d: u32| Utc.with_ymd_and_hms(2024, 5, d, 8, 0, 0).unwrap();
+    
+    let watering = CultivationEvent::new(species.id, EventType::Watering, day(1), Some("500 ml".to_string()));
+    let feeding = CultivationEvent::new(species.id, EventType::Feeding, day(3), None);
+    let custom = CultivationEvent::new(species.id, EventType::Custom("defoliation".to_string()), day(5), None);
+    for event in [&custom, &watering, &feeding] {
+        add_cultivation_event(db.pool(), event).await.expect("Failed to add event");
+    }
+    let orphan = CultivationEvent::new(Uuid::new_v4(), EventType::Pruning, day(2), None);
+    assert!(add_cultivation_event(db.pool(), &orphan).await.is_err(), "Events need an existing species");
+    
+    let in_range = get_cultivation_events(db.pool(), species.id, day(1), day(3)).await.expect("Failed to get events");
+    assert_eq!(in_range, vec![watering.clone(), feeding.clone()]);
+    
+    let recent = get_recent_cultivation_events(db.pool(), species.id, 2).await.expect("Failed to get events");
+    assert_eq!(recent, vec![feeding, custom]);
+}
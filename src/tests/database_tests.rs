@@ -17,7 +17,7 @@ async fn test_in_memory_database_creation() {
 
 #[tokio::test]
 async fn test_file_database_creation() {
-    let temp_path = ":memory:"; // Use memory for testing to avoid file cleanup
+    let _temp_path = ":memory:"; // Use memory for testing to avoid file cleanup
     let config = DatabaseConfig::memory();
     
     let db = BotanicalDatabase::new(config).await;
@@ -43,6 +43,7 @@ async fn test_database_config_creation() {
     assert_eq!(config.url, "sqlite:botanical.db");
     assert_eq!(config.max_connections, 10);
     assert!(config.foreign_keys);
+    assert_eq!(config.slow_query_threshold, Some(crate::database::DEFAULT_SLOW_QUERY_THRESHOLD));
 }
 
 #[tokio::test]
@@ -175,4 +176,296 @@ async fn test_database_close() {
     // After closing, operations should fail
     let result = db.health_check().await;
     assert!(result.is_err(), "Health check should fail after database close");
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_migration_adds_timestamps_to_existing_tables() {
+    let db = BotanicalDatabase::memory().await.expect("Failed to create database");
+    
+    // Simulate a database created before modification timestamps existed
+    sqlx::query("CREATE TABLE families (id TEXT PRIMARY KEY, name TEXT NOT NULL, authority TEXT)")
+        .execute(db.pool())
+        .await
+        .expect("Failed to create legacy families table");
+    sqlx::query("INSERT INTO families (id, name, authority) VALUES ('legacy', 'Rosaceae', 'Juss.')")
+        .execute(db.pool())
+        .await
+        .expect("Failed to insert legacy family");
+    
+    db.migrate().await.expect("Migration should upgrade legacy tables");
+    db.migrate().await.expect("Migration should be idempotent");
+    
+    let row = sqlx::query("SELECT created_at, updated_at FROM families WHERE id = 'legacy'")
+        .fetch_one(db.pool())
+        .await
+        .expect("Timestamp columns should exist");
+    let created_at: Option<String> = row.get("created_at");
+    let updated_at: Option<String> = row.get("updated_at");
+    assert!(created_at.is_some(), "Legacy rows should be stamped on upgrade");
+    assert!(updated_at.is_some(), "Legacy rows should be stamped on upgrade");
+}
+
+#[tokio::test]
+async fn test_migration_merges_legacy_duplicate_taxa() {
+    let db = BotanicalDatabase::memory().await.expect("Failed to create database");
+    
+    // Simulate a database created before taxon names were unique
+    for sql in [
+        "CREATE TABLE families (id TEXT PRIMARY KEY, name TEXT NOT NULL, authority TEXT)",
+        "CREATE TABLE genera (id TEXT PRIMARY KEY, family_id TEXT NOT NULL, name TEXT NOT NULL, authority TEXT)",
+        "CREATE TABLE species (id TEXT PRIMARY KEY, genus_id TEXT NOT NULL, specific_epithet TEXT NOT NULL, authority TEXT, \
+         publication_year INTEGER, conservation_status TEXT)",
+        "INSERT INTO families (id, name, authority) VALUES ('f1', 'Rosaceae', 'Juss.'), ('f2', 'Rosaceae', 'Juss.')",
+        "INSERT INTO genera (id, family_id, name, authority) VALUES ('g1', 'f1', 'Rosa', 'L.'), ('g2', 'f2', 'Rosa', 'L.'), ('g3', 'f2', 'Rubus', 'L.')",
+        "INSERT INTO species (id, genus_id, specific_epithet, authority) VALUES ('s1', 'g1', 'canina', 'L.'), ('s2', 'g2', 'gallica', 'L.')",
+    ] {
+        sqlx::query(sql).execute(db.pool()).await.expect("Failed to set up legacy tables");
+    }
+    
+    db.migrate().await.expect("Migration should merge duplicate taxa");
+    
+    let families: Vec<String> = sqlx::query_scalar("SELECT id FROM families ORDER BY id")
+        .fetch_all(db.pool())
+        .await
+        .expect("Failed to read families");
+    assert_eq!(families, vec!["f1"]);
+    let genera: Vec<(String, String)> = sqlx::query_as("SELECT id, family_id FROM genera ORDER BY id")
+        .fetch_all(db.pool())
+        .await
+        .expect("Failed to read genera");
+    assert_eq!(genera, vec![("g1".to_string(), "f1".to_string()), ("g3".to_string(), "f1".to_string())]);
+    let species: Vec<(String, String)> = sqlx::query_as("SELECT id, genus_id FROM species ORDER BY id")
+        .fetch_all(db.pool())
+        .await
+        .expect("Failed to read species");
+    assert_eq!(species, vec![("s1".to_string(), "g1".to_string()), ("s2".to_string(), "g1".to_string())]);
+}
+
+#[tokio::test]
+async fn test_concurrent_migrations_apply_each_version_once() {
+    let path = std::env::temp_dir().join(format!("botanica-migrate-{}.db", uuid::Uuid::new_v4()));
+    let url = format!("sqlite:{}?mode=rwc", path.display());
+    
+    // Several independently booting instances migrating the same database file
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let config = DatabaseConfig { url: url.clone(), ..DatabaseConfig::default() };
+            tokio::spawn(async move {
+                let db = BotanicalDatabase::new(config).await?;
+                let result = db.migrate().await;
+                db.close().await;
+                result
+            })
+        })
+        .collect();
+    
+    for task in tasks {
+        let result = task.await.expect("Migration task panicked");
+        assert!(result.is_ok(), "Concurrent migration failed: {:?}", result.err());
+    }
+    
+    let db = BotanicalDatabase::new(DatabaseConfig { url, ..DatabaseConfig::default() }).await
+        .expect("Failed to reopen database");
+    let row = sqlx::query("SELECT COUNT(*) AS applied, COUNT(DISTINCT version) AS versions, MAX(version) AS latest FROM schema_migrations")
+        .fetch_one(db.pool())
+        .await
+        .expect("Failed to read schema_migrations");
+    let applied: i64 = row.get("applied");
+    let versions: i64 = row.get("versions");
+    let latest: i64 = row.get("latest");
+    db.close().await;
+    let _ = std::fs::remove_file(&path);
+    
+    assert_eq!(applied, versions, "No migration version should be recorded twice");
+    assert_eq!(applied, latest, "Every migration should have been applied exactly once");
+}
+
+#[tokio::test]
+async fn test_migration_records_versions_once() {
+    let db = create_test_database().await.expect("Failed to create test database");
+    
+    let count_before: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM schema_migrations")
+        .fetch_one(db.pool())
+        .await
+        .expect("schema_migrations should exist after migration");
+    assert!(count_before > 0, "Applied migrations should be recorded");
+    
+    db.migrate().await.expect("Re-running migrations should succeed");
+    
+    let count_after: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM schema_migrations")
+        .fetch_one(db.pool())
+        .await
+        .expect("Failed to count migrations");
+    assert_eq!(count_before, count_after, "Re-running migrations should not apply anything");
+}
+
+/// Captures sqlx statement log records so slow-query logging can be asserted
+struct CapturingLogger {
+    records: std::sync::Mutex<Vec<(log::Level, String)>>,
+}
+
+impl log::Log for CapturingLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+    
+    fn log(&self, record: &log::Record) {
+        if record.target().starts_with("sqlx") {
+            self.records.lock().unwrap().push((record.level(), record.args().to_string()));
+        }
+    }
+    
+    fn flush(&self) {}
+}
+
+fn capturing_logger() -> &'static CapturingLogger {
+    static LOGGER: std::sync::OnceLock<&'static CapturingLogger> = std::sync::OnceLock::new();
+    LOGGER.get_or_init(|| {
+        let logger: &'static CapturingLogger = Box::leak(Box::new(CapturingLogger { records: Default::default() }));
+        log::set_logger(logger).expect("No other logger should be installed in tests");
+        log::set_max_level(log::LevelFilter::Trace);
+        logger
+    })
+}
+
+#[tokio::test]
+async fn test_slow_query_threshold_logging() {
+    let logger = capturing_logger();
+    
+    let config = DatabaseConfig::memory().with_slow_query_threshold(Some(std::time::Duration::ZERO));
+    let db = BotanicalDatabase::new(config).await.expect("Failed to create database");
+    sqlx::query("SELECT 'slow-threshold-enabled-marker'").execute(db.pool()).await.expect("Query failed");
+    
+    let config = DatabaseConfig::memory().with_slow_query_threshold(None);
+    let db = BotanicalDatabase::new(config).await.expect("Failed to create database");
+    sqlx::query("SELECT 'slow-threshold-disabled-marker'").execute(db.pool()).await.expect("Query failed");
+    
+    let records = logger.records.lock().unwrap();
+    assert!(
+        records.iter().any(|(level, msg)| *level == log::Level::Warn && msg.contains("slow-threshold-enabled-marker")),
+        "Statements over the threshold should be logged at warn level"
+    );
+    assert!(
+        !records.iter().any(|(level, msg)| *level == log::Level::Warn && msg.contains("slow-threshold-disabled-marker")),
+        "No slow-statement warning should be logged when the threshold is disabled"
+    );
+}
+
+fn env_lookup<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+    move |name| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+}
+
+#[test]
+fn test_config_from_env_vars() {
+    let config = DatabaseConfig::from_lookup(env_lookup(&[
+        ("DATABASE_URL", "sqlite::memory:"),
+        ("BOTANICA_MAX_CONNECTIONS", "3"),
+        ("BOTANICA_BUSY_TIMEOUT_MS", "250"),
+    ])).expect("Valid environment should parse");
+    assert_eq!(config.url, "sqlite::memory:");
+    assert_eq!(config.max_connections, 3);
+    assert_eq!(config.busy_timeout, std::time::Duration::from_millis(250));
+    
+    let config = DatabaseConfig::from_lookup(env_lookup(&[("DATABASE_URL", "sqlite:app.db")]))
+        .expect("Optional variables may be omitted");
+    assert_eq!(config.max_connections, DatabaseConfig::default().max_connections);
+    assert_eq!(config.busy_timeout, crate::database::DEFAULT_BUSY_TIMEOUT);
+}
+
+#[test]
+fn test_config_from_env_errors_name_the_variable() {
+    let missing = DatabaseConfig::from_lookup(env_lookup(&[])).unwrap_err();
+    assert!(matches!(missing, crate::DatabaseError::ConfigError(_)));
+    assert!(missing.to_string().contains("DATABASE_URL"));
+    
+    for (name, value) in [("BOTANICA_MAX_CONNECTIONS", "ten"), ("BOTANICA_MAX_CONNECTIONS", "0"), ("BOTANICA_BUSY_TIMEOUT_MS", "-5")] {
+        let err = DatabaseConfig::from_lookup(env_lookup(&[("DATABASE_URL", "sqlite::memory:"), (name, value)])).unwrap_err();
+        assert!(matches!(err, crate::DatabaseError::ConfigError(_)), "{}={} should be a config error", name, value);
+        assert!(err.to_string().contains(name), "Error should name {}: {}", name, err);
+    }
+}
+
+#[tokio::test]
+async fn test_pool_honors_max_connections() {
+    let config = DatabaseConfig::memory().with_busy_timeout(std::time::Duration::from_millis(100));
+    let db = BotanicalDatabase::new(config).await.expect("Failed to create database");
+    assert_eq!(db.pool().options().get_max_connections(), 1);
+}
+
+#[tokio::test]
+async fn test_custom_query_escape_hatch() {
+    use crate::database::SqlValue;
+    use crate::queries::species::insert_species;
+    use crate::types::Species;
+    
+    let db = create_test_database().await.expect("Failed to create database");
+    let (_, genus, species) = super::setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let other = Species::new(genus.id, "canina".to_string(), "L.".to_string(), Some(1753), None);
+    insert_species(db.pool(), &other).await.expect("Failed to insert species");
+    
+    let count: i64 = db.query_scalar("SELECT COUNT(*) FROM species WHERE genus_id = ? AND publication_year = ?", &[genus.id.into(), 1753.into()])
+        .await
+        .expect("Scalar query should succeed");
+    assert_eq!(count, 2);
+    
+    let rows: Vec<(String, Option<String>)> = db.query_as(
+        "SELECT specific_epithet, conservation_status FROM species WHERE genus_id = ? ORDER BY specific_epithet",
+        &[SqlValue::from(genus.id)],
+    )
+    .await
+    .expect("Row query should succeed");
+    assert_eq!(rows, vec![("canina".to_string(), None), ("rubiginosa".to_string(), species.conservation_status.clone())]);
+    
+    let missing = db.query_scalar::<String>("SELECT specific_epithet FROM species WHERE id = ?", &["nope".into()]).await;
+    assert!(matches!(missing, Err(crate::DatabaseError::NotFound(_))));
+    
+    let arity = db.query_scalar::<i64>("SELECT COUNT(*) FROM species WHERE genus_id = ?", &[]).await;
+    assert!(matches!(arity, Err(crate::DatabaseError::ValidationError(_))), "Unbound placeholders should be rejected");
+}
+
+#[tokio::test]
+async fn test_clones_share_the_pool() {
+    use crate::queries::species::get_species_by_id;
+    
+    let db = create_test_database().await.expect("Failed to create database");
+    let (_, _, species) = super::setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let handles: Vec<_> = (0..4).map(|_| {
+        let db = db.clone();
+        tokio::spawn(async move { get_species_by_id(db.pool(), species.id).await })
+    }).collect();
+    for handle in handles {
+        let found = handle.await.expect("Task panicked").expect("Failed to get species");
+        assert!(found.is_some(), "Clones should see data written through the original");
+    }
+    
+    db.clone().close().await;
+    assert!(db.pool().is_closed(), "Closing a clone closes the shared pool");
+}
+
+#[tokio::test]
+async fn test_read_pool_serves_reads_and_rejects_writes() {
+    use crate::queries::species::{get_species_by_id, insert_species};
+    use crate::types::Species;
+    
+    let db = create_test_database().await.expect("Failed to create database");
+    assert!(std::ptr::eq(db.read_pool(), db.pool()), "Without a read pool, reads use the primary");
+    
+    let path = std::env::temp_dir().join(format!("botanica-read-pool-{}.db", uuid::Uuid::new_v4()));
+    let config = DatabaseConfig { url: format!("sqlite:{}?mode=rwc", path.display()), ..DatabaseConfig::default() };
+    let db = BotanicalDatabase::new(config).await.expect("Failed to create database");
+    db.migrate().await.expect("Failed to migrate");
+    sqlx::query("PRAGMA journal_mode = WAL").execute(db.pool()).await.expect("Failed to enable WAL");
+    let db = db.with_read_pool(&format!("sqlite:{}", path.display())).await.expect("Failed to open read pool");
+    
+    let (_, genus, species) = super::setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let found = get_species_by_id(db.read_pool(), species.id).await.expect("Failed to read through read pool");
+    assert!(found.is_some(), "Read pool should see the primary's writes");
+    
+    let other = Species::new(genus.id, "canina".to_string(), "L.".to_string(), None, None);
+    assert!(insert_species(db.read_pool(), &other).await.is_err(), "Read pool must reject writes");
+    
+    db.close().await;
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+}
@@ -0,0 +1,98 @@
+//! Partial date tests
+//!
+//! Tests PartialDate parsing, ISO formatting, interval matching and flexible input parsing.
+
+use chrono::{Datelike, NaiveDate};
+use crate::types::{PartialDate, DateOrder, parse_flexible_date, parse_flexible_date_with};
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_partial_date_round_trips_iso_forms() {
+    for text in ["1987", "1987-03", "1987-03-14", "0850"] {
+        let date: PartialDate = text.parse().unwrap_or_else(|e| panic!("{} should parse: {}", text, e));
+        assert_eq!(date.to_string(), text);
+    }
+    assert_eq!("1987-03".parse::<PartialDate>().unwrap(), PartialDate::YearMonth(1987, 3));
+    assert_eq!(PartialDate::Full(ymd(1987, 3, 14)).year(), 1987);
+}
+
+#[test]
+fn test_partial_date_rejects_malformed_input() {
+    for text in ["", "87", "1987-3", "1987-13", "1987-02-30", "1987/03", "1987-03-14-01", "March 1987"] {
+        assert!(text.parse::<PartialDate>().is_err(), "'{}' should be rejected", text);
+    }
+    assert!(PartialDate::year_month(1987, 0).is_err());
+    for text in ["10000", "+987", "-05", "+-1987", "-", "+99999999999"] {
+        assert!(text.parse::<PartialDate>().is_err(), "'{}' should be rejected", text);
+    }
+}
+
+#[test]
+fn test_partial_date_round_trips_expanded_years() {
+    let dates = [
+        (PartialDate::Year(0), "0000"),
+        (PartialDate::Year(9999), "9999"),
+        (PartialDate::Year(10000), "+10000"),
+        (PartialDate::Year(-5), "-0005"),
+        (PartialDate::Year(-12345), "-12345"),
+        (PartialDate::Year(i32::MAX), "+2147483647"),
+        (PartialDate::Year(i32::MIN), "-2147483648"),
+        (PartialDate::YearMonth(-1, 12), "-0001-12"),
+        (PartialDate::YearMonth(10000, 1), "+10000-01"),
+        (PartialDate::Full(ymd(10000, 1, 1)), "+10000-01-01"),
+        (PartialDate::Full(ymd(-5, 3, 14)), "-0005-03-14"),
+        (PartialDate::Full(NaiveDate::MAX), &format!("+{}-12-31", NaiveDate::MAX.year())),
+    ];
+    for (date, text) in dates {
+        assert_eq!(date.to_string(), text);
+        assert_eq!(text.parse::<PartialDate>().unwrap_or_else(|e| panic!("{} should parse: {}", text, e)), date);
+        let through_serde = PartialDate::try_from(String::from(date)).expect("Display output should parse");
+        assert_eq!(through_serde, date);
+    }
+}
+
+#[test]
+fn test_partial_date_interval_matching() {
+    assert_eq!(PartialDate::Year(1987).interval(), Some((ymd(1987, 1, 1), ymd(1987, 12, 31))));
+    assert_eq!(PartialDate::YearMonth(1988, 2).interval(), Some((ymd(1988, 2, 1), ymd(1988, 2, 29))));
+    assert_eq!(PartialDate::YearMonth(1987, 12).interval(), Some((ymd(1987, 12, 1), ymd(1987, 12, 31))));
+    assert_eq!(PartialDate::YearMonth(1987, 13).interval(), None);
+    
+    let march = PartialDate::YearMonth(1987, 3);
+    assert!(march.overlaps(ymd(1987, 3, 20), ymd(1987, 6, 1)));
+    assert!(march.overlaps(ymd(1986, 1, 1), ymd(1987, 3, 1)));
+    assert!(!march.overlaps(ymd(1987, 4, 1), ymd(1987, 12, 31)));
+    assert!(PartialDate::Year(1987).overlaps(ymd(1987, 7, 4), ymd(1987, 7, 4)));
+}
+
+#[test]
+fn test_parse_flexible_date_formats() {
+    let full = PartialDate::Full(ymd(2019, 7, 18));
+    for input in ["2019-07-18", "18/07/2019", "18.07.2019", "July 18, 2019", "Jul 18 2019", "18 July 2019"] {
+        assert_eq!(parse_flexible_date(input).unwrap(), full, "Failed on '{}'", input);
+    }
+    assert_eq!(parse_flexible_date("2019").unwrap(), PartialDate::Year(2019));
+    assert_eq!(parse_flexible_date("2019-07").unwrap(), PartialDate::YearMonth(2019, 7));
+    assert_eq!(parse_flexible_date("July 2019").unwrap(), PartialDate::YearMonth(2019, 7));
+    assert_eq!(parse_flexible_date("ca. 1890").unwrap(), PartialDate::Year(1890));
+}
+
+#[test]
+fn test_parse_flexible_date_locale_hint() {
+    assert_eq!(parse_flexible_date_with("03/07/2019", DateOrder::DayFirst).unwrap(), PartialDate::Full(ymd(2019, 7, 3)));
+    assert_eq!(parse_flexible_date_with("03/07/2019", DateOrder::MonthFirst).unwrap(), PartialDate::Full(ymd(2019, 3, 7)));
+    // Only one reading is a valid date, whatever the hint
+    assert_eq!(parse_flexible_date_with("18/07/2019", DateOrder::MonthFirst).unwrap(), PartialDate::Full(ymd(2019, 7, 18)));
+}
+
+#[test]
+fn test_parse_flexible_date_rejects_garbage() {
+    for input in ["", "unknown", "18/07/19", "1890-1895"] {
+        let err = parse_flexible_date(input).expect_err(input);
+        assert!(matches!(err, crate::DatabaseError::ValidationError(_)));
+        assert!(err.to_string().contains(input), "Error should name '{}'", input);
+    }
+}
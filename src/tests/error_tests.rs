@@ -0,0 +1,62 @@
+//! Error type tests
+//!
+//! Tests error display, source chains, retry classification and API conversion.
+
+use crate::DatabaseError;
+use std::error::Error;
+use std::io;
+
+#[test]
+fn test_network_error_source_chain() {
+    let cause = io::Error::new(io::ErrorKind::TimedOut, "connection timed out");
+    let error = DatabaseError::network_source("Request to ContextLite failed", cause);
+    
+    assert_eq!(error.to_string(), "Network error: Request to ContextLite failed");
+    let source = error.source().expect("Network error should expose its cause");
+    assert_eq!(source.to_string(), "connection timed out");
+    assert!(source.downcast_ref::<io::Error>().is_some(), "Source should keep its concrete type");
+    assert!(error.is_retryable());
+}
+
+#[test]
+fn test_contextlite_error_source_chain() {
+    let error = DatabaseError::contextlite_source("Indexing failed", "workspace not found");
+    
+    assert_eq!(error.to_string(), "ContextLite error: Indexing failed");
+    assert_eq!(error.source().map(|e| e.to_string()), Some("workspace not found".to_string()));
+    assert!(!error.is_retryable());
+}
+
+#[test]
+fn test_errors_without_source() {
+    assert!(DatabaseError::contextlite("plain message").source().is_none());
+    assert!(DatabaseError::network("plain message").source().is_none());
+    assert!(DatabaseError::validation("bad input").source().is_none());
+}
+
+#[test]
+fn test_api_error_codes_and_categories() {
+    use crate::error::{ApiError, ErrorCategory};
+    
+    let error = DatabaseError::validation("Genus name must not be empty");
+    assert_eq!(error.to_api_error(), ApiError {
+        code: "validation_error".to_string(),
+        category: ErrorCategory::Client,
+        message: "Validation error: Genus name must not be empty".to_string(),
+    });
+    assert_eq!(DatabaseError::not_found("x").category(), ErrorCategory::Client);
+    assert_eq!(DatabaseError::config("x").category(), ErrorCategory::Server);
+    assert_eq!(DatabaseError::network("x").code(), "network_error");
+    assert_eq!(DatabaseError::network("x").category(), ErrorCategory::External);
+}
+
+#[test]
+fn test_api_error_hides_sql_details() {
+    use crate::error::ErrorCategory;
+    
+    let error = DatabaseError::from(sqlx::Error::Protocol("near \"SELEC\": syntax error in SELEC * FROM species".to_string()));
+    let api_error = error.to_api_error();
+    assert_eq!(api_error.code, "database_error");
+    assert_eq!(api_error.category, ErrorCategory::Server);
+    assert!(!api_error.message.contains("SELEC"), "SQL must not leak: {}", api_error.message);
+}
@@ -298,4 +298,79 @@ async fn test_family_long_names() {
     
     assert_eq!(retrieved.name.len(), 1000);
     assert_eq!(retrieved.authority.len(), 1000);
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_get_or_create_family_creates_then_reuses() {
+    let db = setup_test_database().await;
+    
+    let (created_id, created) = get_or_create_family(db.pool(), "Rosaceae", "Juss.").await
+        .expect("Failed to create family");
+    assert!(created, "First call should create the family");
+    
+    let (existing_id, created_again) = get_or_create_family(db.pool(), "Rosaceae", "Juss.").await
+        .expect("Failed to fetch existing family");
+    assert!(!created_again, "Second call should reuse the existing family");
+    assert_eq!(created_id, existing_id);
+    
+    let found = get_families_by_name(db.pool(), "Rosaceae").await.expect("Failed to search families");
+    assert_eq!(found.len(), 1, "Only one family row should exist");
+}
+
+#[tokio::test]
+async fn test_get_or_create_family_concurrent() {
+    let db = setup_test_database().await;
+    
+    let tasks: Vec<_> = (0..8)
+        .map(|_| {
+            let pool = db.pool().clone();
+            tokio::spawn(async move { get_or_create_family(&pool, "Orchidaceae", "Juss.").await })
+        })
+        .collect();
+    
+    let mut ids = Vec::new();
+    let mut created_count = 0;
+    for task in tasks {
+        let (id, created) = task.await.expect("Task panicked").expect("get_or_create_family failed");
+        ids.push(id);
+        if created {
+            created_count += 1;
+        }
+    }
+    
+    assert_eq!(created_count, 1, "Exactly one task should create the family");
+    assert!(ids.iter().all(|id| *id == ids[0]), "All tasks should resolve to the same family");
+}
+
+#[test]
+fn test_deterministic_id_strategy_is_rfc4122_v5() {
+    use crate::types::{DeterministicIdStrategy, IdStrategy};
+    
+    let dns = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+    let strategy = DeterministicIdStrategy::with_namespace(dns, "python.org");
+    assert_eq!(strategy.next_id(), Uuid::parse_str("886313e1-3b8a-5372-9b90-0c9aee199e5d").unwrap());
+    assert_eq!(strategy.next_id(), strategy.next_id());
+    assert_ne!(DeterministicIdStrategy::new("gbif:5358748").next_id(), DeterministicIdStrategy::new("gbif:5358749").next_id());
+}
+
+#[tokio::test]
+async fn test_get_or_create_family_with_ids_is_stable_across_imports() {
+    use crate::types::DeterministicIdStrategy;
+    
+    let strategy = DeterministicIdStrategy::new("powo:30000154-2");
+    let first = setup_test_database().await;
+    let second = setup_test_database().await;
+    
+    let (first_id, created) = get_or_create_family_with_ids(first.pool(), "Rosaceae", "Juss.", &strategy).await
+        .expect("Failed to create family");
+    assert!(created);
+    let (second_id, _) = get_or_create_family_with_ids(second.pool(), "Rosaceae", "Juss.", &strategy).await
+        .expect("Failed to create family");
+    assert_eq!(first_id, second_id, "Re-import should reproduce the same ID");
+    
+    // A record that already carries an ID keeps it
+    let family = Family::with_id(Uuid::new_v4(), "Fabaceae".to_string(), "Lindl.".to_string());
+    insert_family(first.pool(), &family).await.expect("Failed to insert family");
+    let stored = get_family_by_id(first.pool(), family.id).await.expect("Failed to get family");
+    assert!(stored.is_some());
+}
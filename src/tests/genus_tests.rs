@@ -27,7 +27,7 @@ async fn test_insert_genus() {
 #[tokio::test]
 async fn test_get_genus_by_id_existing() {
     let db = setup_test_database().await;
-    let (family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     let result = get_genus_by_id(db.pool(), genus.id).await;
     assert!(result.is_ok(), "Failed to get genus by id: {:?}", result.err());
@@ -106,7 +106,7 @@ async fn test_get_genera_by_family_id_nonexistent_family() {
 #[tokio::test]
 async fn test_update_genus_existing() {
     let db = setup_test_database().await;
-    let (family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     let mut updated_genus = genus.clone();
     updated_genus.name = "Updated_Rosa".to_string();
@@ -141,7 +141,7 @@ async fn test_update_genus_nonexistent() {
 #[tokio::test]
 async fn test_update_genus_change_family() {
     let db = setup_test_database().await;
-    let (family1, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family1, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     // Create a second family
     let family2 = Family::new("Poaceae".to_string(), "Barnhart".to_string());
@@ -165,7 +165,7 @@ async fn test_update_genus_change_family() {
 #[tokio::test]
 async fn test_delete_genus_existing() {
     let db = setup_test_database().await;
-    let (family, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     // First delete the dependent species
     use crate::queries::species::delete_species;
@@ -299,4 +299,63 @@ async fn test_multiple_genera_same_name_different_families() {
     assert_eq!(retrieved1.family_id, family1.id);
     assert_eq!(retrieved2.family_id, family2.id);
     assert_eq!(retrieved1.name, retrieved2.name);
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_get_or_create_genus_creates_then_reuses() {
+    let db = setup_test_database().await;
+    let (family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let (existing_id, created) = get_or_create_genus(db.pool(), family.id, &genus.name, &genus.authority).await
+        .expect("Failed to fetch existing genus");
+    assert!(!created, "Existing genus should be reused");
+    assert_eq!(existing_id, genus.id);
+    
+    let (new_id, created) = get_or_create_genus(db.pool(), family.id, "Prunus", "L.").await
+        .expect("Failed to create genus");
+    assert!(created, "Unknown genus should be created");
+    
+    let stored = get_genus_by_id(db.pool(), new_id).await
+        .expect("Failed to get genus")
+        .expect("Created genus should exist");
+    assert_eq!(stored.family_id, family.id);
+    assert_eq!(stored.name, "Prunus");
+}
+
+#[tokio::test]
+async fn test_rename_genus_records_species_name_history() {
+    use crate::queries::species::{get_name_history, get_species_by_scientific_name};
+    
+    let db = setup_test_database().await;
+    let (_, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let renamed = rename_genus(db.pool(), genus.id, " Rhodora ").await.expect("Failed to rename genus");
+    assert!(renamed, "Existing genus should be renamed");
+    
+    let stored = get_genus_by_id(db.pool(), genus.id).await
+        .expect("Failed to get genus")
+        .expect("Genus should exist");
+    assert_eq!(stored.name, "Rhodora");
+    
+    let history = get_name_history(db.pool(), species.id).await.expect("Failed to get history");
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].old_name, "Rosa rubiginosa Linnaeus");
+    assert_eq!(history[0].new_name, "Rhodora rubiginosa Linnaeus");
+    assert_eq!(history[0].reason.as_deref(), Some("Genus renamed to Rhodora"));
+    
+    let found = get_species_by_scientific_name(db.pool(), "Rhodora", "rubiginosa").await
+        .expect("Failed to search by scientific name");
+    assert_eq!(found.map(|s| s.id), Some(species.id));
+}
+
+#[tokio::test]
+async fn test_rename_genus_nonexistent_and_blank() {
+    let db = setup_test_database().await;
+    let (_, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let renamed = rename_genus(db.pool(), Uuid::new_v4(), "Rhodora").await.expect("Rename should not error");
+    assert!(!renamed, "Missing genus should not be renamed");
+    
+    let result = rename_genus(db.pool(), genus.id, "  ").await;
+    assert!(matches!(result, Err(crate::DatabaseError::ValidationError(_))));
+}
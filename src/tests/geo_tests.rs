@@ -0,0 +1,55 @@
+//! Geographic type tests
+//!
+//! Tests GeoPoint validation and distance calculations.
+
+use crate::types::GeoPoint;
+use serde::Deserialize;
+use serde::de::value::{Error as ValueError, MapDeserializer};
+
+#[test]
+fn test_geopoint_valid_ranges() {
+    assert!(GeoPoint::new(0.0, 0.0).is_ok());
+    assert!(GeoPoint::new(90.0, 180.0).is_ok());
+    assert!(GeoPoint::new(-90.0, -180.0).is_ok());
+}
+
+#[test]
+fn test_geopoint_rejects_out_of_range() {
+    assert!(GeoPoint::new(90.1, 0.0).is_err(), "Latitude above 90 should be rejected");
+    assert!(GeoPoint::new(-91.0, 0.0).is_err(), "Latitude below -90 should be rejected");
+    assert!(GeoPoint::new(0.0, 180.5).is_err(), "Longitude above 180 should be rejected");
+    assert!(GeoPoint::new(f64::NAN, 0.0).is_err(), "NaN should be rejected");
+}
+
+#[test]
+fn test_geopoint_from_columns() {
+    let point = GeoPoint::from_columns(Some(51.5), Some(-0.12)).expect("Valid columns should parse");
+    assert_eq!(point, Some(GeoPoint::new(51.5, -0.12).unwrap()));
+    
+    assert_eq!(GeoPoint::from_columns(Some(51.5), None).expect("Missing column is not an error"), None);
+    assert!(GeoPoint::from_columns(Some(200.0), Some(0.0)).is_err());
+}
+
+#[test]
+fn test_geopoint_distance() {
+    let london = GeoPoint::new(51.5074, -0.1278).unwrap();
+    let paris = GeoPoint::new(48.8566, 2.3522).unwrap();
+    
+    let distance = london.distance_km_to(&paris);
+    assert!((distance - 343.5).abs() < 2.0, "London-Paris should be about 343 km, got {}", distance);
+    assert_eq!(london.distance_km_to(&london), 0.0);
+    assert!((london.distance_km_to(&paris) - paris.distance_km_to(&london)).abs() < 1e-9);
+}
+
+#[test]
+fn test_geopoint_deserialize_validates() {
+    let deserialize = |latitude: f64, longitude: f64| {
+        let fields = vec![("latitude", latitude), ("longitude", longitude)];
+        GeoPoint::deserialize(MapDeserializer::<_, ValueError>::new(fields.into_iter()))
+    };
+    
+    let point = deserialize(51.5, -0.12).expect("Valid point should deserialize");
+    assert_eq!((point.latitude(), point.longitude()), (51.5, -0.12));
+    assert!(deserialize(91.0, 0.0).is_err(), "Latitude above 90 should be rejected");
+    assert!(deserialize(0.0, -180.5).is_err(), "Longitude below -180 should be rejected");
+}
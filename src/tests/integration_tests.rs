@@ -95,7 +95,7 @@ async fn test_multiple_genera_per_family() {
     let mut inserted_genera = Vec::new();
     for (name, authority) in genera_data {
         let genus = Genus::new(family.id, name.to_string(), authority.to_string());
-        insert_genus(db.pool(), &genus).await.expect(&format!("Failed to insert genus {}", name));
+        insert_genus(db.pool(), &genus).await.unwrap_or_else(|e| panic!("Failed to insert genus {}: {:?}", name, e));
         inserted_genera.push(genus);
     }
     
@@ -113,7 +113,7 @@ async fn test_multiple_genera_per_family() {
 #[tokio::test]
 async fn test_multiple_species_per_genus() {
     let db = setup_test_database().await;
-    let (family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     // Create multiple species in the same genus
     let species_data = vec![
@@ -132,7 +132,7 @@ async fn test_multiple_species_per_genus() {
             Some(year),
             Some("LC".to_string())
         );
-        insert_species(db.pool(), &species).await.expect(&format!("Failed to insert species {}", epithet));
+        insert_species(db.pool(), &species).await.unwrap_or_else(|e| panic!("Failed to insert species {}: {:?}", epithet, e));
         inserted_species.push(species);
     }
     
@@ -152,12 +152,8 @@ async fn test_delete_family_with_dependent_genera() {
     // Try to delete the family while it has dependent genera
     let result = delete_family(db.pool(), family.id).await;
     
-    // This should either fail due to foreign key constraint or succeed if ON DELETE CASCADE is set
-    // Based on the schema, it should fail
-    if result.is_err() {
-        // Foreign key constraint prevented deletion - this is expected
-        assert!(true, "Foreign key constraint correctly prevented family deletion");
-    } else {
+    // The schema's foreign key should block this; if ON DELETE CASCADE is ever set, it succeeds instead
+    if result.is_ok() {
         // If deletion succeeded, verify that dependent records were also deleted
         let genus_exists = get_genus_by_id(db.pool(), genus.id).await
             .expect("Query should succeed");
@@ -169,6 +165,8 @@ async fn test_delete_family_with_dependent_genera() {
         if genus_exists.is_some() {
             delete_species(db.pool(), species.id).await.ok();
             delete_genus(db.pool(), genus.id).await.ok();
+        } else {
+            assert!(species_exists.is_none(), "Species should be deleted along with their genus");
         }
     }
 }
@@ -176,16 +174,13 @@ async fn test_delete_family_with_dependent_genera() {
 #[tokio::test]
 async fn test_delete_genus_with_dependent_species() {
     let db = setup_test_database().await;
-    let (family, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     // Try to delete the genus while it has dependent species
     let result = delete_genus(db.pool(), genus.id).await;
     
-    // This should either fail due to foreign key constraint or succeed if ON DELETE CASCADE is set
-    if result.is_err() {
-        // Foreign key constraint prevented deletion - this is expected
-        assert!(true, "Foreign key constraint correctly prevented genus deletion");
-    } else {
+    // The schema's foreign key should block this; if ON DELETE CASCADE is ever set, it succeeds instead
+    if result.is_ok() {
         // If deletion succeeded, verify that dependent species were also deleted
         let species_exists = get_species_by_id(db.pool(), species.id).await
             .expect("Query should succeed");
@@ -221,7 +216,7 @@ async fn test_proper_deletion_order() {
 #[tokio::test]
 async fn test_update_genus_family_relationship() {
     let db = setup_test_database().await;
-    let (family1, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family1, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     // Create a second family
     let family2 = Family::new("Poaceae".to_string(), "Barnhart".to_string());
@@ -252,7 +247,7 @@ async fn test_update_genus_family_relationship() {
 #[tokio::test]
 async fn test_update_species_genus_relationship() {
     let db = setup_test_database().await;
-    let (family, genus1, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (family, _genus1, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     // Create a second genus in the same family
     let genus2 = Genus::new(family.id, "Prunus".to_string(), "Linnaeus".to_string());
@@ -356,4 +351,75 @@ async fn test_transaction_rollback_scenario() {
     let genus_exists = get_genus_by_id(db.pool(), genus.id).await
         .expect("Genus query should succeed");
     assert!(genus_exists.is_some(), "Genus should still exist after failed species insert");
-}
\ No newline at end of file
+}
+
+mod prelude_usage {
+    use crate::prelude::*;
+    
+    #[tokio::test]
+    async fn test_prelude_covers_basic_workflow() {
+        let db = create_test_database().await.expect("Failed to create database");
+        let (family_id, _) = get_or_create_family(db.pool(), "Lamiaceae", "Martinov").await.expect("Failed to create family");
+        let (genus_id, _) = get_or_create_genus(db.pool(), family_id, "Salvia", "L.").await.expect("Failed to create genus");
+        let species = Species::new(genus_id, "officinalis".to_string(), "L.".to_string(), Some(1753), None);
+        insert_species(db.pool(), &species).await.expect("Failed to insert species");
+        
+        let found: Option<Species> = get_species_by_scientific_name(db.pool(), "Salvia", "officinalis").await
+            .expect("Failed to look up species");
+        assert_eq!(found.map(|s| s.id), Some(species.id));
+    }
+}
+
+#[tokio::test]
+async fn test_dataset_etag_tracks_writes() {
+    use crate::queries::dataset::dataset_etag;
+    
+    let db = setup_test_database().await;
+    let empty = dataset_etag(db.pool()).await.expect("Failed to compute etag");
+    let (_, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let after_insert = dataset_etag(db.pool()).await.expect("Failed to compute etag");
+    assert_ne!(empty, after_insert, "Inserts should change the etag");
+    assert_eq!(after_insert, dataset_etag(db.pool()).await.unwrap(), "The etag should be stable without writes");
+    assert_eq!(after_insert.len(), 16);
+    
+    let mut updated = species.clone();
+    updated.publication_year = Some(1754);
+    update_species(db.pool(), species.id, &updated).await.expect("Failed to update species");
+    let after_update = dataset_etag(db.pool()).await.unwrap();
+    assert_ne!(after_insert, after_update, "Updates should change the etag");
+    
+    let other = Species::new(genus.id, "canina".to_string(), "L.".to_string(), None, None);
+    insert_species(db.pool(), &other).await.expect("Failed to insert species");
+    let before_delete = dataset_etag(db.pool()).await.unwrap();
+    delete_species(db.pool(), species.id).await.expect("Failed to delete species");
+    assert_ne!(before_delete, dataset_etag(db.pool()).await.unwrap(), "Deletes should change the etag");
+}
+
+#[tokio::test]
+async fn test_dataset_etag_tracks_species_details() {
+    use crate::queries::annotations::{add_annotation, resolve_annotation};
+    use crate::queries::dataset::dataset_etag;
+    use crate::types::Annotation;
+    
+    let db = setup_test_database().await;
+    let (_, _, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let before = dataset_etag(db.pool()).await.expect("Failed to compute etag");
+    let annotation = Annotation::new(species.id, "curator".to_string(), "Check authority".to_string());
+    add_annotation(db.pool(), &annotation).await.expect("Failed to add annotation");
+    let after_annotation = dataset_etag(db.pool()).await.unwrap();
+    assert_ne!(before, after_annotation, "Annotations should change the etag");
+    
+    resolve_annotation(db.pool(), annotation.id).await.expect("Failed to resolve annotation");
+    let after_resolve = dataset_etag(db.pool()).await.unwrap();
+    assert_ne!(after_annotation, after_resolve, "Updates without a timestamp should change the etag");
+    
+    sqlx::query("INSERT INTO specimens (id, species_id) VALUES (?, ?)")
+        .bind(Uuid::new_v4().to_string())
+        .bind(species.id.to_string())
+        .execute(db.pool())
+        .await
+        .expect("Failed to insert specimen");
+    assert_ne!(after_resolve, dataset_etag(db.pool()).await.unwrap(), "Specimens should change the etag");
+}
@@ -0,0 +1,241 @@
+//! Data integrity audit and repair tests
+//!
+//! Tests detection of orphaned records and duplicate taxa, and their repair.
+
+use super::{setup_test_database, setup_sample_taxonomy};
+use crate::queries::integrity::*;
+use crate::queries::family::insert_family;
+use crate::queries::genus::insert_genus;
+use crate::queries::species::{insert_species, get_name_history};
+use crate::types::{Family, Genus, Species};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_audit_clean_database_has_no_findings() {
+    let db = setup_test_database().await;
+    setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+
+    let findings = audit_integrity(db.pool()).await.expect("Failed to audit");
+    assert!(findings.is_empty(), "Unexpected findings: {:?}", findings);
+}
+
+#[tokio::test]
+async fn test_audit_finds_orphans() {
+    let db = setup_test_database().await;
+    let (_, _, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+
+    let missing_genus = Uuid::new_v4();
+    let missing_family = Uuid::new_v4();
+    let missing_species = Uuid::new_v4();
+    let orphan_species = Uuid::new_v4();
+    let orphan_genus = Uuid::new_v4();
+    let orphan_specimen = Uuid::new_v4();
+
+    let mut conn = db.pool().acquire().await.expect("Failed to acquire connection");
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await.unwrap();
+    sqlx::query("INSERT INTO species (id, genus_id, specific_epithet) VALUES (?, ?, 'lost')")
+        .bind(orphan_species.to_string())
+        .bind(missing_genus.to_string())
+        .execute(&mut *conn).await.unwrap();
+    sqlx::query("INSERT INTO genera (id, family_id, name) VALUES (?, ?, 'Lostus')")
+        .bind(orphan_genus.to_string())
+        .bind(missing_family.to_string())
+        .execute(&mut *conn).await.unwrap();
+    sqlx::query("INSERT INTO specimens (id, species_id) VALUES (?, ?)")
+        .bind(orphan_specimen.to_string())
+        .bind(missing_species.to_string())
+        .execute(&mut *conn).await.unwrap();
+    sqlx::query("INSERT INTO specimens (id, species_id) VALUES (?, ?)")
+        .bind(Uuid::new_v4().to_string())
+        .bind(species.id.to_string())
+        .execute(&mut *conn).await.unwrap();
+    sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await.unwrap();
+    drop(conn);
+
+    let findings = audit_integrity(db.pool()).await.expect("Failed to audit");
+    assert_eq!(findings.len(), 3, "Unexpected findings: {:?}", findings);
+
+    let species_finding = findings.iter().find(|f| f.kind == FindingKind::OrphanedSpecies).expect("Missing species finding");
+    assert_eq!(species_finding.affected_id, orphan_species);
+    assert_eq!(species_finding.related_id, Some(missing_genus));
+    assert!(species_finding.message.contains(&missing_genus.to_string()));
+
+    let genus_finding = findings.iter().find(|f| f.kind == FindingKind::OrphanedGenus).expect("Missing genus finding");
+    assert_eq!(genus_finding.affected_id, orphan_genus);
+
+    let specimen_finding = findings.iter().find(|f| f.kind == FindingKind::OrphanedSpecimen).expect("Missing specimen finding");
+    assert_eq!(specimen_finding.affected_id, orphan_specimen);
+    assert_eq!(specimen_finding.related_id, Some(missing_species));
+}
+
+#[tokio::test]
+async fn test_audit_finds_normalized_duplicates() {
+    let db = setup_test_database().await;
+    let (family, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+
+    let duplicate_family = Family::new(" rosaceae".to_string(), "JUSSIEU".to_string());
+    insert_family(db.pool(), &duplicate_family).await.expect("Failed to insert family");
+    let duplicate_species = Species::new(genus.id, "Rubiginosa ".to_string(), "linnaeus".to_string(), None, None);
+    insert_species(db.pool(), &duplicate_species).await.expect("Failed to insert species");
+    // Same epithet under a different authority is a homonym, not a duplicate
+    let homonym = Species::new(genus.id, "rubiginosa".to_string(), "Smith".to_string(), None, None);
+    insert_species(db.pool(), &homonym).await.expect("Failed to insert species");
+
+    let findings = audit_integrity(db.pool()).await.expect("Failed to audit");
+    assert_eq!(findings.len(), 2, "Unexpected findings: {:?}", findings);
+
+    let family_finding = findings.iter().find(|f| f.kind == FindingKind::DuplicateFamily).expect("Missing family finding");
+    assert_eq!(family_finding.affected_id, duplicate_family.id);
+    assert_eq!(family_finding.related_id, Some(family.id));
+
+    let species_finding = findings.iter().find(|f| f.kind == FindingKind::DuplicateSpecies).expect("Missing species finding");
+    assert_eq!(species_finding.affected_id, duplicate_species.id);
+    assert_eq!(species_finding.related_id, Some(species.id));
+}
+
+async fn insert_orphans(db: &crate::BotanicalDatabase) -> (Uuid, Uuid) {
+    let orphan_species = Uuid::new_v4();
+    let orphan_specimen = Uuid::new_v4();
+
+    let mut conn = db.pool().acquire().await.expect("Failed to acquire connection");
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await.unwrap();
+    sqlx::query("INSERT INTO species (id, genus_id, specific_epithet, authority) VALUES (?, ?, 'lost', 'L.')")
+        .bind(orphan_species.to_string())
+        .bind(Uuid::new_v4().to_string())
+        .execute(&mut *conn).await.unwrap();
+    sqlx::query("INSERT INTO specimens (id, species_id) VALUES (?, ?)")
+        .bind(orphan_specimen.to_string())
+        .bind(Uuid::new_v4().to_string())
+        .execute(&mut *conn).await.unwrap();
+    sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await.unwrap();
+
+    (orphan_species, orphan_specimen)
+}
+
+#[tokio::test]
+async fn test_repair_dry_run_writes_nothing() {
+    let db = setup_test_database().await;
+    setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    insert_orphans(&db).await;
+
+    let findings = audit_integrity(db.pool()).await.expect("Failed to audit");
+    let summary = repair_integrity(db.pool(), &findings, true, true).await.expect("Failed to repair");
+    assert!(summary.dry_run);
+    assert_eq!(summary.species_reassigned, 1);
+    assert_eq!(summary.specimens_deleted, 1);
+
+    let after = audit_integrity(db.pool()).await.expect("Failed to audit");
+    assert_eq!(after, findings, "Dry run must not change the database");
+}
+
+#[tokio::test]
+async fn test_repair_reassigns_orphans_and_requires_flag_for_deletes() {
+    let db = setup_test_database().await;
+    setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (orphan_species, _) = insert_orphans(&db).await;
+
+    let findings = audit_integrity(db.pool()).await.expect("Failed to audit");
+    let summary = repair_integrity(db.pool(), &findings, false, false).await.expect("Failed to repair");
+    assert_eq!(summary.species_reassigned, 1);
+    assert_eq!(summary.specimens_deleted, 0);
+    assert_eq!(summary.skipped, 1, "Specimen delete should be skipped without allow_deletes");
+
+    let genus_name: String = sqlx::query_scalar("SELECT g.name FROM species s JOIN genera g ON s.genus_id = g.id WHERE s.id = ?")
+        .bind(orphan_species.to_string())
+        .fetch_one(db.pool())
+        .await
+        .expect("Orphan should now have a genus");
+    assert_eq!(genus_name, INCERTAE_SEDIS);
+
+    let remaining = audit_integrity(db.pool()).await.expect("Failed to audit");
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].kind, FindingKind::OrphanedSpecimen);
+
+    let summary = repair_integrity(db.pool(), &remaining, false, true).await.expect("Failed to repair");
+    assert_eq!(summary.specimens_deleted, 1);
+    assert!(audit_integrity(db.pool()).await.expect("Failed to audit").is_empty());
+}
+
+#[tokio::test]
+async fn test_repair_merges_duplicates() {
+    let db = setup_test_database().await;
+    let (_, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+
+    let duplicate = Species::new(genus.id, "RUBIGINOSA".to_string(), "Linnaeus".to_string(), None, None);
+    insert_species(db.pool(), &duplicate).await.expect("Failed to insert species");
+    let specimen_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO specimens (id, species_id) VALUES (?, ?)")
+        .bind(specimen_id.to_string())
+        .bind(duplicate.id.to_string())
+        .execute(db.pool())
+        .await
+        .expect("Failed to insert specimen");
+
+    let findings = audit_integrity(db.pool()).await.expect("Failed to audit");
+    let summary = repair_integrity(db.pool(), &findings, false, false).await.expect("Failed to repair");
+    assert_eq!(summary.taxa_merged, 0);
+    assert_eq!(summary.skipped, 1, "Merge should be skipped without allow_deletes");
+    assert_eq!(audit_integrity(db.pool()).await.expect("Failed to audit"), findings);
+
+    let summary = repair_integrity(db.pool(), &findings, false, true).await.expect("Failed to repair");
+    assert_eq!(summary.taxa_merged, 1);
+
+    let species_id: String = sqlx::query_scalar("SELECT species_id FROM specimens WHERE id = ?")
+        .bind(specimen_id.to_string())
+        .fetch_one(db.pool())
+        .await
+        .expect("Specimen should survive the merge");
+    assert_eq!(species_id, species.id.to_string());
+    assert!(audit_integrity(db.pool()).await.expect("Failed to audit").is_empty());
+}
+
+#[tokio::test]
+async fn test_repair_merge_keeps_name_history() {
+    let db = setup_test_database().await;
+    let (_, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+
+    let duplicate = Species::new(genus.id, "Rubiginosa".to_string(), "Linnaeus".to_string(), None, None);
+    insert_species(db.pool(), &duplicate).await.expect("Failed to insert species");
+    sqlx::query("INSERT INTO name_history (species_id, old_name, new_name, changed_at, reason) VALUES (?, 'Rosa eglanteria L.', 'Rosa rubiginosa L.', ?, 'Synonymised')")
+        .bind(duplicate.id.to_string())
+        .bind(chrono::Utc::now())
+        .execute(db.pool())
+        .await
+        .expect("Failed to insert name change");
+
+    let findings = audit_integrity(db.pool()).await.expect("Failed to audit");
+    let summary = repair_integrity(db.pool(), &findings, false, true).await.expect("Failed to repair");
+    assert_eq!(summary.taxa_merged, 1);
+
+    let history = get_name_history(db.pool(), species.id).await.expect("Failed to get name history");
+    assert_eq!(history.len(), 1, "Name history should move to the kept species");
+    assert_eq!(history[0].old_name, "Rosa eglanteria L.");
+    assert_eq!(history[0].reason.as_deref(), Some("Synonymised"));
+}
+
+#[tokio::test]
+async fn test_repair_merges_families_with_colliding_genera() {
+    let db = setup_test_database().await;
+    let (family, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+
+    let duplicate_family = Family::new("ROSACEAE".to_string(), family.authority.clone());
+    insert_family(db.pool(), &duplicate_family).await.expect("Failed to insert family");
+    let twin_genus = Genus::new(duplicate_family.id, genus.name.clone(), genus.authority.clone());
+    insert_genus(db.pool(), &twin_genus).await.expect("Failed to insert genus");
+    let twin_species = Species::new(twin_genus.id, "canina".to_string(), "L.".to_string(), None, None);
+    insert_species(db.pool(), &twin_species).await.expect("Failed to insert species");
+
+    let findings = audit_integrity(db.pool()).await.expect("Failed to audit");
+    assert_eq!(findings.len(), 1, "Unexpected findings: {:?}", findings);
+    let summary = repair_integrity(db.pool(), &findings, false, true).await.expect("Failed to repair");
+    assert_eq!(summary.taxa_merged, 2, "The colliding genus and the family should both merge");
+
+    let genus_ids: Vec<String> = sqlx::query_scalar("SELECT genus_id FROM species WHERE id IN (?, ?)")
+        .bind(species.id.to_string())
+        .bind(twin_species.id.to_string())
+        .fetch_all(db.pool())
+        .await
+        .expect("Failed to query species");
+    assert_eq!(genus_ids, vec![genus.id.to_string(), genus.id.to_string()]);
+    assert!(audit_integrity(db.pool()).await.expect("Failed to audit").is_empty());
+}
@@ -13,6 +13,16 @@ pub mod species_tests;
 pub mod genus_tests;
 pub mod family_tests;
 pub mod integration_tests;
+pub mod conservation_tests;
+pub mod geo_tests;
+pub mod error_tests;
+pub mod search_tests;
+pub mod integrity_tests;
+pub mod date_tests;
+pub mod accession_tests;
+pub mod completeness_tests;
+pub mod care_tests;
+pub mod cultivation_tests;
 
 /// Helper function to create a test database with sample data
 pub async fn setup_test_database() -> BotanicalDatabase {
@@ -73,6 +83,8 @@ pub fn assert_species_eq(expected: &Species, actual: &Species) {
     assert_eq!(expected.authority, actual.authority, "Authority mismatch");
     assert_eq!(expected.publication_year, actual.publication_year, "Publication year mismatch");
     assert_eq!(expected.conservation_status, actual.conservation_status, "Conservation status mismatch");
+    assert_eq!(expected.source, actual.source, "Source mismatch");
+    assert_eq!(expected.source_record_id, actual.source_record_id, "Source record ID mismatch");
 }
 
 /// Helper function to assert genus equality with better error messages
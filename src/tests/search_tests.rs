@@ -0,0 +1,167 @@
+//! Search and autocomplete tests
+//!
+//! Tests name suggestions and other search entry points.
+
+use super::{setup_test_database, setup_sample_taxonomy};
+use crate::queries::search::*;
+use crate::queries::genus::insert_genus;
+use crate::queries::species::insert_species;
+use crate::types::{Genus, Species};
+use sqlx::Row;
+
+#[tokio::test]
+async fn test_suggest_names_prefers_larger_genera() {
+    let db = setup_test_database().await;
+    let (family, rosa, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    for epithet in ["canina", "gallica"] {
+        let species = Species::new(rosa.id, epithet.to_string(), "L.".to_string(), None, None);
+        insert_species(db.pool(), &species).await.expect("Failed to insert species");
+    }
+    let rubus = Genus::new(family.id, "Rubus".to_string(), "L.".to_string());
+    insert_genus(db.pool(), &rubus).await.expect("Failed to insert genus");
+    let idaeus = Species::new(rubus.id, "idaeus".to_string(), "L.".to_string(), None, None);
+    insert_species(db.pool(), &idaeus).await.expect("Failed to insert species");
+    
+    let suggestions = suggest_names(db.pool(), "r", 10).await.expect("Failed to suggest names");
+    let names: Vec<&str> = suggestions.iter().map(|s| s.scientific_name.as_str()).collect();
+    assert_eq!(names, vec!["Rosa canina", "Rosa gallica", "Rosa rubiginosa", "Rubus idaeus"]);
+    assert_eq!(suggestions[0].genus_species_count, 3);
+    assert_eq!(suggestions[3].species_id, idaeus.id);
+    
+    let limited = suggest_names(db.pool(), "r", 2).await.expect("Failed to suggest names");
+    assert_eq!(limited.len(), 2, "Suggestions should respect the limit");
+}
+
+#[tokio::test]
+async fn test_suggest_names_matches_epithet_and_binomial() {
+    let db = setup_test_database().await;
+    let (_, _, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let by_epithet = suggest_names(db.pool(), "RUBIG", 10).await.expect("Failed to suggest names");
+    assert_eq!(by_epithet.len(), 1);
+    assert_eq!(by_epithet[0].species_id, species.id);
+    
+    let by_binomial = suggest_names(db.pool(), "rosa rub", 10).await.expect("Failed to suggest names");
+    assert_eq!(by_binomial.len(), 1);
+    
+    let wrong_genus = suggest_names(db.pool(), "Ros rub", 10).await.expect("Failed to suggest names");
+    assert!(wrong_genus.is_empty(), "Binomial queries should match the genus exactly");
+}
+
+#[tokio::test]
+async fn test_suggest_names_treats_wildcards_literally() {
+    let db = setup_test_database().await;
+    setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    assert!(suggest_names(db.pool(), "%", 10).await.expect("Query should succeed").is_empty());
+    assert!(suggest_names(db.pool(), "_osa", 10).await.expect("Query should succeed").is_empty());
+    assert!(suggest_names(db.pool(), "   ", 10).await.expect("Query should succeed").is_empty());
+}
+
+#[tokio::test]
+async fn test_search_taxa_by_keyword_scoped_by_rank() {
+    let db = setup_test_database().await;
+    let (family, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let rosmarinus = Genus::new(family.id, "Rosmarinus".to_string(), "L.".to_string());
+    insert_genus(db.pool(), &rosmarinus).await.expect("Failed to insert genus");
+    let canina = Species::new(genus.id, "canina".to_string(), "L.".to_string(), None, None);
+    insert_species(db.pool(), &canina).await.expect("Failed to insert species");
+    
+    let all = search_taxa_by_keyword(db.pool(), "ros", None).await.expect("Failed to search");
+    let labels: Vec<String> = all.iter().map(|hit| hit.to_string()).collect();
+    assert_eq!(labels, vec![
+        "Family: Rosaceae",
+        "Genus: Rosa",
+        "Genus: Rosmarinus",
+        "Species: Rosa canina",
+        "Species: Rosa rubiginosa",
+    ]);
+    
+    let genera = search_taxa_by_keyword(db.pool(), "ROS", Some(TaxonRank::Genus)).await.expect("Failed to search");
+    assert_eq!(genera.len(), 2);
+    assert!(genera.iter().all(|hit| hit.rank() == TaxonRank::Genus));
+    
+    let species_hits = search_taxa_by_keyword(db.pool(), "rosa rubig", Some(TaxonRank::Species)).await.expect("Failed to search");
+    assert_eq!(species_hits.len(), 1);
+    assert_eq!(species_hits[0].id(), species.id);
+    
+    let families = search_taxa_by_keyword(db.pool(), "canina", Some(TaxonRank::Family)).await.expect("Failed to search");
+    assert!(families.is_empty());
+    assert!(search_taxa_by_keyword(db.pool(), " ", None).await.expect("Failed to search").is_empty());
+}
+
+#[tokio::test]
+async fn test_suggest_names_ranked_by_views() {
+    use crate::queries::stats::record_species_view;
+    
+    let db = setup_test_database().await;
+    let (_, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let canina = Species::new(genus.id, "canina".to_string(), "L.".to_string(), None, None);
+    insert_species(db.pool(), &canina).await.expect("Failed to insert species");
+    record_species_view(db.pool(), species.id).await.expect("Failed to record view");
+    
+    let plain = suggest_names(db.pool(), "Rosa", 10).await.expect("Failed to suggest");
+    assert_eq!(plain[0].species_id, canina.id);
+    let ranked = suggest_names_with(db.pool(), "Rosa", 10, true).await.expect("Failed to suggest");
+    assert_eq!(ranked[0].species_id, species.id);
+    assert_eq!(ranked.len(), 2);
+}
+
+#[tokio::test]
+async fn test_search_species_resolving_synonyms() {
+    use crate::queries::species::update_species;
+    
+    let db = setup_test_database().await;
+    let (_, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    // rubiginosa was formerly known as eglanteria
+    let mut renamed = species.clone();
+    renamed.specific_epithet = "eglanteria".to_string();
+    update_species(db.pool(), species.id, &renamed).await.expect("Failed to rename");
+    renamed.specific_epithet = "rubiginosa".to_string();
+    update_species(db.pool(), species.id, &renamed).await.expect("Failed to rename back");
+    let eglantina = Species::new(genus.id, "eglantina".to_string(), "L.".to_string(), None, None);
+    insert_species(db.pool(), &eglantina).await.expect("Failed to insert species");
+    
+    let found = search_species_resolving_synonyms(db.pool(), "rosa EGLANT").await.expect("Failed to search");
+    assert_eq!(found.len(), 2, "Each species should appear once: {:?}", found);
+    assert_eq!(found[0].species.id, eglantina.id);
+    assert!(found[0].match_note().is_none(), "Current-name matches carry no note");
+    assert_eq!(found[1].species.id, species.id);
+    assert_eq!(found[1].scientific_name, "Rosa rubiginosa");
+    assert_eq!(found[1].match_note().as_deref(), Some("matched via synonym: Rosa eglanteria Linnaeus"));
+    
+    // Its current name also matched a former name, but the direct match wins
+    let found = search_species_resolving_synonyms(db.pool(), "rubiginosa").await.expect("Failed to search");
+    assert_eq!(found.len(), 1);
+    assert!(found[0].matched_synonym.is_none());
+    assert!(search_species_resolving_synonyms(db.pool(), "Prunus").await.expect("Failed to search").is_empty());
+}
+
+#[tokio::test]
+async fn test_suggest_names_uses_indexes() {
+    let db = setup_test_database().await;
+    
+    for (two_words, rank_by_views) in [(false, false), (true, false), (false, true)] {
+        let sql = format!("EXPLAIN QUERY PLAN {}", suggest_names_sql(two_words, rank_by_views));
+        let plan: Vec<String> = sqlx::query(&sql)
+            .bind("ro%")
+            .bind("ro%")
+            .bind(10_i64)
+            .fetch_all(db.pool())
+            .await
+            .expect("Failed to explain query")
+            .iter()
+            .map(|row| row.get::<String, _>("detail"))
+            .collect();
+        
+        assert!(!plan.is_empty());
+        for step in &plan {
+            assert!(
+                !["SCAN s", "SCAN g", "SCAN species", "SCAN genera"].iter().any(|scan| step.starts_with(scan)),
+                "Full table scan in {:?}", plan
+            );
+        }
+    }
+}
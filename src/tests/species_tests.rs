@@ -10,7 +10,7 @@ use uuid::Uuid;
 #[tokio::test]
 async fn test_insert_species() {
     let db = setup_test_database().await;
-    let (family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     let new_species = Species::new(
         genus.id,
@@ -27,7 +27,7 @@ async fn test_insert_species() {
 #[tokio::test]
 async fn test_get_species_by_id_existing() {
     let db = setup_test_database().await;
-    let (family, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, _genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     let result = get_species_by_id(db.pool(), species.id).await;
     assert!(result.is_ok(), "Failed to get species by id: {:?}", result.err());
@@ -54,7 +54,7 @@ async fn test_get_species_by_id_nonexistent() {
 #[tokio::test]
 async fn test_get_species_by_name_exact_match() {
     let db = setup_test_database().await;
-    let (family, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, _genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     let result = get_species_by_name(db.pool(), "rubiginosa").await;
     assert!(result.is_ok(), "Failed to get species by name: {:?}", result.err());
@@ -67,7 +67,7 @@ async fn test_get_species_by_name_exact_match() {
 #[tokio::test]
 async fn test_get_species_by_name_partial_match() {
     let db = setup_test_database().await;
-    let (family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     // Insert multiple species with similar names
     let species1 = Species::new(
@@ -110,7 +110,7 @@ async fn test_get_species_by_name_no_match() {
 #[tokio::test]
 async fn test_update_species_existing() {
     let db = setup_test_database().await;
-    let (family, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, _genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     let mut updated_species = species.clone();
     updated_species.conservation_status = Some("NT".to_string());
@@ -132,7 +132,7 @@ async fn test_update_species_existing() {
 #[tokio::test]
 async fn test_update_species_nonexistent() {
     let db = setup_test_database().await;
-    let (family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     let nonexistent_id = Uuid::new_v4();
     let fake_species = create_test_species(genus.id);
@@ -145,7 +145,7 @@ async fn test_update_species_nonexistent() {
 #[tokio::test]
 async fn test_delete_species_existing() {
     let db = setup_test_database().await;
-    let (family, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, _genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     let result = delete_species(db.pool(), species.id).await;
     assert!(result.is_ok(), "Failed to delete species: {:?}", result.err());
@@ -187,7 +187,7 @@ async fn test_species_foreign_key_constraint() {
 #[tokio::test]
 async fn test_species_data_integrity() {
     let db = setup_test_database().await;
-    let (family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     // Test with empty specific epithet
     let species_empty_name = Species::new(
@@ -217,7 +217,7 @@ async fn test_species_data_integrity() {
 #[tokio::test]
 async fn test_species_with_optional_fields() {
     let db = setup_test_database().await;
-    let (family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     // Test species with no publication year or conservation status
     let minimal_species = Species::new(
@@ -242,7 +242,7 @@ async fn test_species_with_optional_fields() {
 #[tokio::test]
 async fn test_species_with_negative_publication_year() {
     let db = setup_test_database().await;
-    let (family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let (_family, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
     
     // Test species with negative publication year (BCE)
     let ancient_species = Species::new(
@@ -293,4 +293,468 @@ async fn test_species_methods() {
     species_no_status.set_conservation_status(Some("CR".to_string()));
     assert!(species_no_status.has_conservation_status());
     assert_eq!(species_no_status.get_conservation_status(), Some("CR"));
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_get_species_by_scientific_name_existing() {
+    let db = setup_test_database().await;
+    let (_, _, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let found = get_species_by_scientific_name(db.pool(), "Rosa", "rubiginosa").await
+        .expect("Failed to get species by scientific name")
+        .expect("Species should be found");
+    assert_species_eq(&species, &found);
+}
+
+#[tokio::test]
+async fn test_get_species_by_scientific_name_case_insensitive() {
+    let db = setup_test_database().await;
+    let (_, _, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let found = get_species_by_scientific_name(db.pool(), "ROSA", "Rubiginosa").await
+        .expect("Failed to get species by scientific name");
+    assert_eq!(found.map(|s| s.id), Some(species.id));
+}
+
+#[tokio::test]
+async fn test_get_species_by_scientific_name_absent() {
+    let db = setup_test_database().await;
+    setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    // Epithet exists but under a different genus name
+    let found = get_species_by_scientific_name(db.pool(), "Prunus", "rubiginosa").await
+        .expect("Query should succeed for absent names");
+    assert!(found.is_none(), "No species should be found for an unknown binomial");
+}
+
+#[tokio::test]
+async fn test_get_species_modified_since() {
+    let db = setup_test_database().await;
+    let (_, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let checkpoint = chrono::Utc::now();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    
+    let unchanged = get_species_modified_since(db.pool(), checkpoint).await
+        .expect("Failed to get modified species");
+    assert!(unchanged.is_empty(), "Nothing should have changed since the checkpoint");
+    
+    let new_species = create_test_species(genus.id);
+    insert_species(db.pool(), &new_species).await.expect("Failed to insert species");
+    
+    let mut updated = species.clone();
+    updated.conservation_status = Some("NT".to_string());
+    update_species(db.pool(), species.id, &updated).await.expect("Failed to update species");
+    
+    let changed = get_species_modified_since(db.pool(), checkpoint).await
+        .expect("Failed to get modified species");
+    let ids: Vec<Uuid> = changed.iter().map(|s| s.id).collect();
+    assert_eq!(ids, vec![new_species.id, species.id], "Insert and update should both be reported in change order");
+}
+
+#[tokio::test]
+async fn test_get_species_page_walks_all_rows() {
+    let db = setup_test_database().await;
+    let (_, genus, first) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let mut expected = vec![first.id];
+    for epithet in ["gallica", "canina", "rugosa", "damascena"] {
+        let species = Species::new(genus.id, epithet.to_string(), "L.".to_string(), None, None);
+        insert_species(db.pool(), &species).await.expect("Failed to insert species");
+        expected.push(species.id);
+    }
+    expected.sort();
+    
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    let mut pages = 0;
+    loop {
+        let page = get_species_page(db.pool(), cursor, 2).await.expect("Failed to get species page");
+        assert!(page.species.len() <= 2, "Page should respect the limit");
+        seen.extend(page.species.iter().map(|s| s.id));
+        pages += 1;
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    
+    assert_eq!(seen, expected, "Pages should cover every species exactly once in ID order");
+    assert_eq!(pages, 3);
+}
+
+#[tokio::test]
+async fn test_get_species_page_exact_multiple_has_no_trailing_page() {
+    let db = setup_test_database().await;
+    let (_, genus, _) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    insert_species(db.pool(), &create_test_species(genus.id)).await.expect("Failed to insert species");
+    
+    let page = get_species_page(db.pool(), None, 2).await.expect("Failed to get species page");
+    assert_eq!(page.species.len(), 2);
+    assert!(page.next_cursor.is_none(), "A full final page should not advertise another page");
+    
+    assert!(get_species_page(db.pool(), None, 0).await.is_err(), "Zero limit should be rejected");
+}
+
+#[tokio::test]
+async fn test_species_attributes() {
+    use crate::queries::attributes::*;
+    
+    let db = setup_test_database().await;
+    let (_, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let other = Species::new(genus.id, "canina".to_string(), "L.".to_string(), None, None);
+    insert_species(db.pool(), &other).await.expect("Failed to insert species");
+    
+    set_species_attribute(db.pool(), species.id, "growth_habit", "shrub").await.expect("Failed to set attribute");
+    set_species_attribute(db.pool(), species.id, "toxicity", "low").await.expect("Failed to set attribute");
+    set_species_attribute(db.pool(), species.id, "toxicity", "none").await.expect("Failed to overwrite attribute");
+    set_species_attribute(db.pool(), other.id, "growth_habit", "shrub").await.expect("Failed to set attribute");
+    assert!(set_species_attribute(db.pool(), species.id, "  ", "x").await.is_err(), "Empty key should be rejected");
+    
+    let attributes = get_species_attributes(db.pool(), species.id).await.expect("Failed to get attributes");
+    assert_eq!(attributes.len(), 2);
+    assert_eq!(attributes["toxicity"], "none");
+    
+    let shrubs = find_species_by_attribute(db.pool(), "growth_habit", "shrub").await.expect("Failed to find species");
+    let ids: Vec<_> = shrubs.iter().map(|s| s.id).collect();
+    assert_eq!(ids, vec![other.id, species.id]);
+    assert!(find_species_by_attribute(db.pool(), "toxicity", "low").await.expect("Failed to find species").is_empty());
+    
+    delete_species(db.pool(), species.id).await.expect("Failed to delete species");
+    assert!(get_species_attributes(db.pool(), species.id).await.expect("Failed to get attributes").is_empty());
+}
+
+#[tokio::test]
+async fn test_species_media() {
+    use crate::queries::media::*;
+    use crate::types::Media;
+    
+    let db = setup_test_database().await;
+    let (_, _, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let photo = Media::new(
+        species.id,
+        "https://example.org/rosa.jpg".to_string(),
+        "StillImage".to_string(),
+        "CC-BY-4.0".to_string(),
+        Some("A. Botanist".to_string()),
+        Some("Flowering shoot".to_string()),
+    );
+    let recording = Media::new(species.id, "https://example.org/rosa.mp4".to_string(), "MovingImage".to_string(), "CC0-1.0".to_string(), None, None);
+    add_media(db.pool(), &photo).await.expect("Failed to add media");
+    add_media(db.pool(), &recording).await.expect("Failed to add media");
+    
+    let unlicensed = Media::new(species.id, "https://example.org/x.jpg".to_string(), "StillImage".to_string(), " ".to_string(), None, None);
+    assert!(add_media(db.pool(), &unlicensed).await.is_err(), "Media without a license should be rejected");
+    
+    let media = get_media_for_species(db.pool(), species.id).await.expect("Failed to get media");
+    assert_eq!(media, vec![photo.clone(), recording.clone()]);
+    
+    assert!(delete_media(db.pool(), photo.id).await.expect("Failed to delete media"));
+    assert!(!delete_media(db.pool(), photo.id).await.expect("Failed to delete media"));
+    
+    delete_species(db.pool(), species.id).await.expect("Media should not block species deletion");
+    assert!(get_media_for_species(db.pool(), species.id).await.expect("Failed to get media").is_empty());
+}
+
+#[tokio::test]
+async fn test_name_history_records_name_changes() {
+    let db = setup_test_database().await;
+    let (_, _, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    let mut updated = species.clone();
+    updated.conservation_status = Some("NT".to_string());
+    update_species(db.pool(), species.id, &updated).await.expect("Failed to update species");
+    assert!(get_name_history(db.pool(), species.id).await.expect("Failed to get history").is_empty(),
+        "Non-name edits should not be recorded");
+    
+    updated.specific_epithet = "eglanteria".to_string();
+    update_species_with_reason(db.pool(), species.id, &updated, Some("Nomenclatural correction")).await
+        .expect("Failed to update species");
+    updated.authority = "L.".to_string();
+    update_species(db.pool(), species.id, &updated).await.expect("Failed to update species");
+    
+    let history = get_name_history(db.pool(), species.id).await.expect("Failed to get history");
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].old_name, "Rosa rubiginosa Linnaeus");
+    assert_eq!(history[0].new_name, "Rosa eglanteria Linnaeus");
+    assert_eq!(history[0].reason.as_deref(), Some("Nomenclatural correction"));
+    assert_eq!(history[1].old_name, "Rosa eglanteria Linnaeus");
+    assert_eq!(history[1].new_name, "Rosa eglanteria L.");
+    assert!(history[1].reason.is_none());
+    assert!(history[0].changed_at <= history[1].changed_at);
+}
+
+#[test]
+fn test_species_diff() {
+    let existing = create_test_species(Uuid::new_v4());
+    assert!(existing.diff(&existing.clone()).is_empty(), "Identical records should have no changes");
+    
+    let mut proposed = existing.clone();
+    proposed.specific_epithet = "eglanteria".to_string();
+    proposed.publication_year = None;
+    proposed.conservation_status = Some("EN".to_string());
+    
+    let changes = existing.diff(&proposed);
+    let fields: Vec<&str> = changes.iter().map(|c| c.field).collect();
+    assert_eq!(fields, vec!["specific_epithet", "publication_year"]);
+    assert_eq!(changes[0].old_value.as_deref(), Some("rubiginosa"));
+    assert_eq!(changes[0].new_value.as_deref(), Some("eglanteria"));
+    assert_eq!(changes[1].old_value.as_deref(), Some("1753"));
+    assert_eq!(changes[1].new_value, None);
+    
+    proposed = existing.clone();
+    proposed.genus_id = Uuid::new_v4();
+    let changes = existing.diff(&proposed);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].field, "genus_id");
+    assert_eq!(changes[0].new_value, Some(proposed.genus_id.to_string()));
+}
+
+#[tokio::test]
+async fn test_get_related_species() {
+    use crate::queries::attributes::set_species_attribute;
+    use crate::queries::genus::insert_genus;
+    use crate::types::Genus;
+    
+    let db = setup_test_database().await;
+    let (family, rosa, target) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let rubus = Genus::new(family.id, "Rubus".to_string(), "L.".to_string());
+    insert_genus(db.pool(), &rubus).await.expect("Failed to insert genus");
+    
+    let canina = Species::new(rosa.id, "canina".to_string(), "L.".to_string(), None, None);
+    let gallica = Species::new(rosa.id, "gallica".to_string(), "L.".to_string(), None, None);
+    let idaeus = Species::new(rubus.id, "idaeus".to_string(), "L.".to_string(), None, None);
+    for species in [&canina, &gallica, &idaeus] {
+        insert_species(db.pool(), species).await.expect("Failed to insert species");
+    }
+    set_species_attribute(db.pool(), target.id, "habit", "shrub").await.expect("Failed to set attribute");
+    set_species_attribute(db.pool(), gallica.id, "habit", "shrub").await.expect("Failed to set attribute");
+    set_species_attribute(db.pool(), idaeus.id, "habit", "shrub").await.expect("Failed to set attribute");
+    
+    let related = get_related_species(db.pool(), target.id, 10).await.expect("Failed to get related species");
+    let ids: Vec<_> = related.iter().map(|s| s.id).collect();
+    assert_eq!(ids, vec![gallica.id, canina.id, idaeus.id], "Congeners first, ranked by shared attributes");
+    
+    let limited = get_related_species(db.pool(), target.id, 2).await.expect("Failed to get related species");
+    assert_eq!(limited.len(), 2);
+    assert!(limited.iter().all(|s| s.id != target.id));
+}
+
+#[tokio::test]
+async fn test_delete_species_batch() {
+    use crate::queries::attributes::set_species_attribute;
+    
+    let db = setup_test_database().await;
+    let (_, genus, with_specimen) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let plain = Species::new(genus.id, "canina".to_string(), "L.".to_string(), None, None);
+    insert_species(db.pool(), &plain).await.expect("Failed to insert species");
+    set_species_attribute(db.pool(), plain.id, "habit", "shrub").await.expect("Failed to set attribute");
+    crate::queries::stats::record_species_view(db.pool(), plain.id).await.expect("Failed to record view");
+    let garden = crate::queries::collections::create_collection(db.pool(), "Garden").await.expect("Failed to create collection");
+    crate::queries::collections::add_to_collection(db.pool(), garden.id, plain.id).await.expect("Failed to add to collection");
+    sqlx::query("INSERT INTO specimens (id, species_id) VALUES (?, ?)")
+        .bind(Uuid::new_v4().to_string())
+        .bind(with_specimen.id.to_string())
+        .execute(db.pool())
+        .await
+        .expect("Failed to insert specimen");
+    
+    let ids = [with_specimen.id, plain.id, Uuid::new_v4()];
+    let err = delete_species_batch(db.pool(), &ids, false).await.expect_err("Specimens should block deletion");
+    assert!(matches!(err, crate::DatabaseError::ConstraintViolation(_)));
+    assert!(err.to_string().contains(&with_specimen.id.to_string()));
+    assert!(get_species_by_id(db.pool(), plain.id).await.unwrap().is_some(), "A blocked batch must delete nothing");
+    
+    let summary = delete_species_batch(db.pool(), &ids, true).await.expect("Cascading delete should succeed");
+    assert_eq!(summary.species, 2);
+    assert_eq!(summary.specimens, 1);
+    assert_eq!(summary.attributes, 1);
+    assert_eq!(summary.stats, 1);
+    assert_eq!(summary.collection_members, 1);
+    assert!(get_species_by_id(db.pool(), with_specimen.id).await.unwrap().is_none());
+    let attributes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM species_attributes")
+        .fetch_one(db.pool())
+        .await
+        .expect("Failed to count attributes");
+    assert_eq!(attributes, 0, "Attributes should cascade with their species");
+}
+
+#[tokio::test]
+async fn test_species_source_tracking() {
+    use crate::types::DataSource;
+    
+    let db = setup_test_database().await;
+    let (_, genus, manual) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let from_gbif = Species::new(genus.id, "canina".to_string(), "L.".to_string(), None, None)
+        .with_source(DataSource::Gbif, Some("3003240".to_string()));
+    let from_partner = Species::new(genus.id, "gallica".to_string(), "L.".to_string(), None, None)
+        .with_source(DataSource::Import("kew-2024".to_string()), None);
+    insert_species(db.pool(), &from_gbif).await.expect("Failed to insert species");
+    insert_species(db.pool(), &from_partner).await.expect("Failed to insert species");
+    
+    let stored = get_species_by_id(db.pool(), from_gbif.id).await.unwrap().expect("Species should exist");
+    assert_species_eq(&from_gbif, &stored);
+    assert_eq!(manual.source, DataSource::Manual, "Species default to manual entry");
+    
+    let gbif = get_species_by_source(db.pool(), &DataSource::Gbif).await.expect("Failed to query by source");
+    assert_eq!(gbif.iter().map(|s| s.id).collect::<Vec<_>>(), vec![from_gbif.id]);
+    let partner = get_species_by_source(db.pool(), &DataSource::Import("kew-2024".to_string())).await.expect("Failed to query by source");
+    assert_eq!(partner.len(), 1);
+    assert_eq!(get_species_by_source(db.pool(), &DataSource::Manual).await.unwrap().len(), 1);
+    
+    for text in ["manual", "gbif", "inaturalist", "import:kew-2024"] {
+        assert_eq!(text.parse::<DataSource>().unwrap().to_string(), text);
+    }
+    assert!("import:".parse::<DataSource>().is_err());
+    assert!("GBIF".parse::<DataSource>().is_err());
+}
+
+#[tokio::test]
+async fn test_species_annotations() {
+    use crate::queries::annotations::*;
+    use crate::types::Annotation;
+    
+    let db = setup_test_database().await;
+    let (_, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let other = Species::new(genus.id, "canina".to_string(), "L.".to_string(), None, None);
+    insert_species(db.pool(), &other).await.expect("Failed to insert species");
+    
+    let verify = Annotation::new(species.id, "curator".to_string(), "Authority needs verification, see Smith 2001".to_string());
+    let typo = Annotation::new(species.id, "curator".to_string(), "Check epithet spelling".to_string());
+    let elsewhere = Annotation::new(other.id, "reviewer".to_string(), "Missing publication year".to_string());
+    for annotation in [&verify, &typo, &elsewhere] {
+        add_annotation(db.pool(), annotation).await.expect("Failed to add annotation");
+    }
+    let empty = Annotation::new(species.id, "curator".to_string(), "  ".to_string());
+    assert!(add_annotation(db.pool(), &empty).await.is_err(), "Empty notes should be rejected");
+    
+    assert!(resolve_annotation(db.pool(), typo.id).await.expect("Failed to resolve"));
+    assert!(!resolve_annotation(db.pool(), typo.id).await.expect("Failed to resolve"), "Already resolved");
+    
+    let on_species = get_annotations(db.pool(), species.id).await.expect("Failed to get annotations");
+    assert_eq!(on_species.len(), 2);
+    assert!(on_species.iter().any(|a| a.id == typo.id && a.resolved));
+    
+    let open = get_open_annotations(db.pool()).await.expect("Failed to get open annotations");
+    assert_eq!(open.iter().map(|a| a.id).collect::<Vec<_>>(), vec![verify.id, elsewhere.id]);
+    assert_eq!(open[0], verify);
+}
+
+#[tokio::test]
+async fn test_species_view_stats() {
+    use crate::queries::stats::*;
+    
+    let db = setup_test_database().await;
+    let (_, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let canina = Species::new(genus.id, "canina".to_string(), "L.".to_string(), None, None);
+    let gallica = Species::new(genus.id, "gallica".to_string(), "L.".to_string(), None, None);
+    insert_species(db.pool(), &canina).await.expect("Failed to insert species");
+    insert_species(db.pool(), &gallica).await.expect("Failed to insert species");
+    
+    assert!(get_species_stats(db.pool(), gallica.id).await.expect("Failed to get stats").is_none());
+    for _ in 0..3 {
+        record_species_view(db.pool(), gallica.id).await.expect("Failed to record view");
+    }
+    record_species_view(db.pool(), species.id).await.expect("Failed to record view");
+    assert!(record_species_view(db.pool(), Uuid::new_v4()).await.is_err(), "Unknown species should be rejected");
+    
+    let stats = get_species_stats(db.pool(), gallica.id).await
+        .expect("Failed to get stats")
+        .expect("Viewed species should have stats");
+    assert_eq!(stats.view_count, 3);
+    
+    let most_viewed = get_most_viewed_species(db.pool(), 10).await.expect("Failed to get most viewed");
+    assert_eq!(most_viewed.iter().map(|s| s.id).collect::<Vec<_>>(), vec![gallica.id, species.id]);
+    
+    // Plain ordering is alphabetical; view ranking puts gallica first
+    let related = get_related_species(db.pool(), species.id, 10).await.expect("Failed to get related");
+    assert_eq!(related.iter().map(|s| s.id).collect::<Vec<_>>(), vec![canina.id, gallica.id]);
+    let related = get_related_species_with(db.pool(), species.id, 10, true).await.expect("Failed to get related");
+    assert_eq!(related.iter().map(|s| s.id).collect::<Vec<_>>(), vec![gallica.id, canina.id]);
+}
+
+#[tokio::test]
+async fn test_record_species_view_detached() {
+    use crate::queries::stats::*;
+    
+    let db = setup_test_database().await;
+    let (_, _, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    
+    record_species_view_detached(db.pool(), species.id);
+    for _ in 0..50 {
+        if get_species_stats(db.pool(), species.id).await.expect("Failed to get stats").is_some() {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    panic!("Detached view was never recorded");
+}
+
+#[tokio::test]
+async fn test_species_collections() {
+    use crate::queries::collections::*;
+    
+    let db = setup_test_database().await;
+    let (_, genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let canina = Species::new(genus.id, "canina".to_string(), "L.".to_string(), None, None);
+    insert_species(db.pool(), &canina).await.expect("Failed to insert species");
+    
+    let garden = create_collection(db.pool(), " My Garden ").await.expect("Failed to create collection");
+    let trip = create_collection(db.pool(), "2024 Field Trip").await.expect("Failed to create collection");
+    assert_eq!(garden.name, "My Garden");
+    assert!(create_collection(db.pool(), "  ").await.is_err(), "Blank names should be rejected");
+    
+    assert!(add_to_collection(db.pool(), garden.id, canina.id).await.expect("Failed to add"));
+    assert!(add_to_collection(db.pool(), garden.id, species.id).await.expect("Failed to add"));
+    assert!(!add_to_collection(db.pool(), garden.id, species.id).await.expect("Failed to add"), "Already a member");
+    assert!(add_to_collection(db.pool(), trip.id, species.id).await.expect("Failed to add"));
+    assert!(add_to_collection(db.pool(), garden.id, Uuid::new_v4()).await.is_err(), "Unknown species should be rejected");
+    
+    let members = get_collection_species(db.pool(), garden.id).await.expect("Failed to get members");
+    assert_eq!(members.iter().map(|s| s.id).collect::<Vec<_>>(), vec![canina.id, species.id]);
+    
+    let collections = get_species_collections(db.pool(), species.id).await.expect("Failed to get collections");
+    assert_eq!(collections, vec![trip.clone(), garden.clone()]);
+    
+    assert!(remove_from_collection(db.pool(), garden.id, canina.id).await.expect("Failed to remove"));
+    assert!(!remove_from_collection(db.pool(), garden.id, canina.id).await.expect("Failed to remove"));
+    let members = get_collection_species(db.pool(), garden.id).await.expect("Failed to get members");
+    assert_eq!(members.len(), 1);
+    
+    delete_species(db.pool(), species.id).await.expect("Failed to delete species");
+    assert!(get_collection_species(db.pool(), garden.id).await.expect("Failed to get members").is_empty());
+}
+
+#[tokio::test]
+async fn test_context_documents_follow_species() {
+    use crate::contextlite::BotanicalContext;
+    use crate::queries::context_documents::*;
+    
+    let db = setup_test_database().await;
+    let (_family, _genus, species) = setup_sample_taxonomy(&db).await.expect("Failed to setup taxonomy");
+    let context = BotanicalContext::new("http://localhost:8090", "test-token", "garden")
+        .expect("Failed to create context");
+    
+    let document_id = context.reindex_species(db.pool(), &species, &[]).await.expect("Failed to index");
+    assert_eq!(document_id, format!("species-{}", species.id));
+    let record = get_context_document(db.pool(), species.id, "garden").await
+        .expect("Failed to get document")
+        .expect("Document should be recorded");
+    assert_eq!(record.document_id, document_id);
+    assert!(!record.pending_removal);
+    assert!(get_context_document(db.pool(), species.id, "other").await.expect("Failed to get document").is_none());
+    
+    record_context_document(db.pool(), species.id, "garden", "legacy-doc").await.expect("Failed to record");
+    let document_id = context.reindex_species(db.pool(), &species, &[]).await.expect("Failed to reindex");
+    assert_eq!(document_id, "legacy-doc");
+    
+    assert_eq!(context.purge_deleted_species(db.pool()).await.expect("Failed to purge"), 0);
+    delete_species(db.pool(), species.id).await.expect("Failed to delete species");
+    let pending = get_documents_pending_removal(db.pool(), "garden").await.expect("Failed to get pending");
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].document_id, "legacy-doc");
+    
+    assert_eq!(context.purge_deleted_species(db.pool()).await.expect("Failed to purge"), 1);
+    assert!(get_context_document(db.pool(), species.id, "garden").await.expect("Failed to get document").is_none());
+}
@@ -0,0 +1,90 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use crate::error::DatabaseError;
+
+/// A living-collection accession number such as `2024-0137` or `2024-0137*A`.
+///
+/// The year and per-year sequence identify the accession; an optional
+/// qualifier of uppercase letters distinguishes clonal divisions of it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AccessionNumber {
+    /// Year the accession was received
+    pub year: i32,
+
+    /// Sequence number within the year, starting at 1
+    pub sequence: u32,
+
+    /// Qualifier for a clonal division, e.g. `A`
+    pub qualifier: Option<String>,
+}
+
+impl AccessionNumber {
+    /// Creates an unqualified accession number.
+    pub fn new(year: i32, sequence: u32) -> Self {
+        Self { year, sequence, qualifier: None }
+    }
+
+    /// Returns this accession with a clonal division qualifier, which must be
+    /// one to three uppercase ASCII letters.
+    pub fn with_qualifier(&self, qualifier: &str) -> Result<Self, DatabaseError> {
+        if qualifier.is_empty() || qualifier.len() > 3 || !qualifier.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(DatabaseError::validation(format!(
+                "Accession qualifier '{}' must be 1-3 uppercase letters", qualifier
+            )));
+        }
+        Ok(Self { qualifier: Some(qualifier.to_string()), ..self.clone() })
+    }
+
+    /// The accession this number belongs to, without any qualifier.
+    pub fn base(&self) -> Self {
+        Self::new(self.year, self.sequence)
+    }
+}
+
+impl fmt::Display for AccessionNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:04}", self.year, self.sequence)?;
+        if let Some(qualifier) = &self.qualifier {
+            write!(f, "*{}", qualifier)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for AccessionNumber {
+    type Err = DatabaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DatabaseError::validation(format!("Invalid accession number '{}'", s));
+        let (number, qualifier) = match s.trim().split_once('*') {
+            Some((number, qualifier)) => (number, Some(qualifier)),
+            None => (s.trim(), None),
+        };
+        let (year, sequence) = number.split_once('-').ok_or_else(invalid)?;
+        if year.len() != 4 || sequence.len() < 4 || !year.bytes().chain(sequence.bytes()).all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let accession = Self::new(year.parse().map_err(|_| invalid())?, sequence.parse().map_err(|_| invalid())?);
+        match qualifier {
+            Some(qualifier) => accession.with_qualifier(qualifier),
+            None => Ok(accession),
+        }
+    }
+}
+
+impl TryFrom<String> for AccessionNumber {
+    type Error = DatabaseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AccessionNumber> for String {
+    fn from(accession: AccessionNumber) -> Self {
+        accession.to_string()
+    }
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+/// A curator's note on a species record, tracked until resolved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    /// Unique identifier for the annotation
+    pub id: Uuid,
+    
+    /// Reference to the annotated species
+    pub species_id: Uuid,
+    
+    /// Who wrote the note
+    pub author: String,
+    
+    /// The note itself, e.g. "Authority needs verification, see Smith 2001"
+    pub body: String,
+    
+    /// When the note was written
+    pub created_at: DateTime<Utc>,
+    
+    /// Whether the issue raised has been dealt with
+    pub resolved: bool,
+}
+
+impl Annotation {
+    /// Creates a new unresolved Annotation with a generated UUID.
+    pub fn new(species_id: Uuid, author: String, body: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            species_id,
+            author,
+            body,
+            created_at: Utc::now(),
+            resolved: false,
+        }
+    }
+}
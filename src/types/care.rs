@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use super::cultivation::GrowthStage;
+
+/// A recommended care task for a growth stage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CareAction {
+    /// What to do, e.g. "Reduce nitrogen feeding"
+    pub action: String,
+
+    /// How often to repeat it in days; `None` for a one-off task
+    pub interval_days: Option<u32>,
+}
+
+impl CareAction {
+    /// Creates a new CareAction.
+    pub fn new(action: impl Into<String>, interval_days: Option<u32>) -> Self {
+        Self { action: action.into(), interval_days }
+    }
+}
+
+/// Built-in actions as (stage, action, interval in days)
+const DEFAULT_ACTIONS: &[(GrowthStage, &str, Option<u32>)] = &[
+    (GrowthStage::Seed, "Store seed cool, dry and dark", None),
+    (GrowthStage::Seed, "Check stored seed for mould or pests", Some(30)),
+    (GrowthStage::Germination, "Keep the medium evenly moist", Some(1)),
+    (GrowthStage::Germination, "Check temperature stays in the germination range", Some(1)),
+    (GrowthStage::Seedling, "Water lightly at the base", Some(2)),
+    (GrowthStage::Seedling, "Check seedlings for stretching and adjust light height", Some(3)),
+    (GrowthStage::Seedling, "Harden off before transplanting", None),
+    (GrowthStage::Vegetative, "Water when the top of the medium is dry", Some(3)),
+    (GrowthStage::Vegetative, "Feed with a nitrogen-rich fertiliser", Some(14)),
+    (GrowthStage::Vegetative, "Prune or train for structure", Some(7)),
+    (GrowthStage::Vegetative, "Inspect leaves for pests and disease", Some(7)),
+    (GrowthStage::Flowering, "Reduce nitrogen and increase phosphorus and potassium", Some(14)),
+    (GrowthStage::Flowering, "Check flowers and trichomes for maturity", Some(3)),
+    (GrowthStage::Flowering, "Support heavy stems", Some(7)),
+    (GrowthStage::Flowering, "Inspect flowers for mould", Some(3)),
+    (GrowthStage::Harvest, "Harvest at peak maturity", None),
+    (GrowthStage::Harvest, "Label and weigh the harvest", None),
+    (GrowthStage::Drying, "Hang in a dark, ventilated space", None),
+    (GrowthStage::Drying, "Check room temperature and humidity", Some(1)),
+    (GrowthStage::Drying, "Test stems for dryness", Some(2)),
+    (GrowthStage::Curing, "Open curing containers to exchange air", Some(1)),
+    (GrowthStage::Curing, "Check moisture content", Some(7)),
+];
+
+/// Care actions for each growth stage.
+///
+/// `CareSchedule::default()` holds the built-in actions, which cover every
+/// stage. Custom schedules can be built with `with_action` or deserialized.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CareSchedule {
+    actions: HashMap<GrowthStage, Vec<CareAction>>,
+}
+
+impl CareSchedule {
+    /// Creates a schedule with no actions.
+    pub fn empty() -> Self {
+        Self { actions: HashMap::new() }
+    }
+
+    /// Returns the schedule with an action appended to a stage.
+    pub fn with_action(mut self, stage: GrowthStage, action: CareAction) -> Self {
+        self.actions.entry(stage).or_default().push(action);
+        self
+    }
+
+    /// Actions for a stage, in the order they were added.
+    pub fn actions_for(&self, stage: &GrowthStage) -> &[CareAction] {
+        self.actions.get(stage).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+impl Default for CareSchedule {
+    fn default() -> Self {
+        DEFAULT_ACTIONS
+            .iter()
+            .fold(Self::empty(), |schedule, (stage, action, interval)| {
+                schedule.with_action(stage.clone(), CareAction::new(*action, *interval))
+            })
+    }
+}
+
+/// Built-in care actions for a growth stage
+pub fn care_schedule(stage: GrowthStage) -> Vec<CareAction> {
+    CareSchedule::default().actions_for(&stage).to_vec()
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+/// A user-defined list of species, such as "My Garden" or "2024 Field Trip".
+///
+/// Independent of the taxonomy: a species may belong to any number of collections.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Collection {
+    /// Unique identifier for the collection
+    pub id: Uuid,
+    
+    /// Display name
+    pub name: String,
+    
+    /// When the collection was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl Collection {
+    /// Creates a new Collection with a generated UUID.
+    pub fn new(name: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            created_at: Utc::now(),
+        }
+    }
+}
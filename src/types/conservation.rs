@@ -1 +1,301 @@
-{implement_models_conservation_part}
\ No newline at end of file
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use crate::error::DatabaseError;
+
+/// An IUCN Red List category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum IUCNCategory {
+    Extinct,
+    ExtinctInTheWild,
+    CriticallyEndangered,
+    Endangered,
+    Vulnerable,
+    NearThreatened,
+    LeastConcern,
+    DataDeficient,
+    NotEvaluated,
+}
+
+impl IUCNCategory {
+    /// All categories, from most to least severe
+    pub const ALL: [IUCNCategory; 9] = [
+        IUCNCategory::Extinct,
+        IUCNCategory::ExtinctInTheWild,
+        IUCNCategory::CriticallyEndangered,
+        IUCNCategory::Endangered,
+        IUCNCategory::Vulnerable,
+        IUCNCategory::NearThreatened,
+        IUCNCategory::LeastConcern,
+        IUCNCategory::DataDeficient,
+        IUCNCategory::NotEvaluated,
+    ];
+
+    /// The two-letter Red List code, e.g. `EN`
+    pub fn code(&self) -> &'static str {
+        match self {
+            IUCNCategory::Extinct => "EX",
+            IUCNCategory::ExtinctInTheWild => "EW",
+            IUCNCategory::CriticallyEndangered => "CR",
+            IUCNCategory::Endangered => "EN",
+            IUCNCategory::Vulnerable => "VU",
+            IUCNCategory::NearThreatened => "NT",
+            IUCNCategory::LeastConcern => "LC",
+            IUCNCategory::DataDeficient => "DD",
+            IUCNCategory::NotEvaluated => "NE",
+        }
+    }
+
+    /// The official full name in a language, falling back to English
+    ///
+    /// `lang` is a language tag such as `fr` or `es-MX`; only the primary
+    /// subtag is used. English, French and Spanish are available.
+    pub fn localized_name(&self, lang: &str) -> String {
+        let primary = lang.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+        let index = IUCNCategory::ALL.iter().position(|category| category == self).unwrap_or_default();
+        let names = match primary.as_str() {
+            "fr" => &FRENCH_NAMES,
+            "es" => &SPANISH_NAMES,
+            _ => &ENGLISH_NAMES,
+        };
+        names[index].to_string()
+    }
+
+    /// Whether the category is one of the threatened categories (`CR`, `EN`, `VU`)
+    pub fn is_threatened(&self) -> bool {
+        matches!(
+            self,
+            IUCNCategory::CriticallyEndangered | IUCNCategory::Endangered | IUCNCategory::Vulnerable
+        )
+    }
+}
+
+/// Official category names, in the order of `IUCNCategory::ALL`
+const ENGLISH_NAMES: [&str; 9] = [
+    "Extinct",
+    "Extinct in the Wild",
+    "Critically Endangered",
+    "Endangered",
+    "Vulnerable",
+    "Near Threatened",
+    "Least Concern",
+    "Data Deficient",
+    "Not Evaluated",
+];
+
+const FRENCH_NAMES: [&str; 9] = [
+    "Éteint",
+    "Éteint à l'état sauvage",
+    "En danger critique",
+    "En danger",
+    "Vulnérable",
+    "Quasi menacé",
+    "Préoccupation mineure",
+    "Données insuffisantes",
+    "Non évalué",
+];
+
+const SPANISH_NAMES: [&str; 9] = [
+    "Extinto",
+    "Extinto en Estado Silvestre",
+    "En Peligro Crítico",
+    "En Peligro",
+    "Vulnerable",
+    "Casi Amenazado",
+    "Preocupación Menor",
+    "Datos Insuficientes",
+    "No Evaluado",
+];
+
+/// The language-neutral two-letter code; see `localized_name` for full names
+impl fmt::Display for IUCNCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for IUCNCategory {
+    type Err = DatabaseError;
+
+    /// Parses a two-letter code, ignoring case and surrounding whitespace
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s.trim().to_ascii_uppercase();
+        IUCNCategory::ALL
+            .into_iter()
+            .find(|category| category.code() == code)
+            .ok_or_else(|| DatabaseError::validation(format!("Unknown IUCN category '{}'", s.trim())))
+    }
+}
+
+/// A single IUCN Red List criterion, e.g. `A2acd` or `B1ab(iii,v)+2ab(iii)`.
+///
+/// Criteria lists combine one or more of these separated by `;`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Criterion {
+    /// The criterion letter (`A` to `E`)
+    pub letter: char,
+
+    /// Numbered subcriteria joined by `+`; empty for criteria that stand alone (`D`, `E`)
+    pub subcriteria: Vec<Subcriterion>,
+}
+
+/// A numbered subcriterion such as `2acd` or `1ab(iii,v)`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subcriterion {
+    /// The subcriterion number
+    pub number: u8,
+
+    /// Lowercase qualifiers, each with any roman-numeral details in parentheses
+    pub qualifiers: Vec<Qualifier>,
+}
+
+/// A lowercase qualifier such as `a` or `b(iii,v)`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Qualifier {
+    /// The qualifier letter
+    pub letter: char,
+
+    /// Roman-numeral details, e.g. `["iii", "v"]` for `b(iii,v)`
+    pub details: Vec<String>,
+}
+
+impl Criterion {
+    /// Highest subcriterion number allowed for a criterion letter, and whether
+    /// the letter may appear without any subcriterion.
+    fn subcriterion_rules(letter: char) -> (u8, bool) {
+        match letter {
+            'A' => (4, false),
+            'B' | 'C' => (2, false),
+            'D' => (2, true),
+            _ => (0, true),
+        }
+    }
+
+    /// Qualifier letters allowed after a numbered subcriterion, how many of
+    /// them are required, and which of them must carry roman-numeral details.
+    ///
+    /// Follows the IUCN 3.1 grammar: `A1`-`A4` need at least one of `a`-`e`
+    /// (`A3` cannot use `a`), `B1`/`B2` need at least two of `a`-`c` with
+    /// details on `b` and `c`, `C2` needs `a` or `b` with details on `a`, and
+    /// `C1`, `D1` and `D2` take none.
+    fn qualifier_rules(letter: char, number: u8) -> (&'static str, usize, &'static str) {
+        match (letter, number) {
+            ('A', 3) => ("bcde", 1, ""),
+            ('A', _) => ("abcde", 1, ""),
+            ('B', _) => ("abc", 2, "bc"),
+            ('C', 2) => ("ab", 1, "a"),
+            _ => ("", 0, ""),
+        }
+    }
+}
+
+impl fmt::Display for Criterion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.letter)?;
+        for (i, sub) in self.subcriteria.iter().enumerate() {
+            if i > 0 {
+                write!(f, "+")?;
+            }
+            write!(f, "{}", sub.number)?;
+            for qualifier in &sub.qualifiers {
+                write!(f, "{}", qualifier.letter)?;
+                if !qualifier.details.is_empty() {
+                    write!(f, "({})", qualifier.details.join(","))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse and validate an IUCN criteria string such as `"A2acd; B1ab(iii)+2ab(iii)"`.
+///
+/// Criteria `A` to `C` require at least one numbered subcriterion, `D` may stand
+/// alone or take `1`/`2`, and `E` takes none. Subcriteria that need qualifiers,
+/// such as `A2`, `B1` or `C2`, are rejected without them. Returns a `ValidationError` naming the
+/// offending token when the string does not follow the grammar.
+pub fn parse_iucn_criteria(s: &str) -> Result<Vec<Criterion>, DatabaseError> {
+    let tokens: Vec<&str> = s.split(';').map(str::trim).filter(|t| !t.is_empty()).collect();
+    if tokens.is_empty() {
+        return Err(DatabaseError::validation("IUCN criteria string is empty"));
+    }
+
+    tokens.into_iter().map(parse_criterion).collect()
+}
+
+fn parse_criterion(token: &str) -> Result<Criterion, DatabaseError> {
+    let invalid = |reason: &str| {
+        DatabaseError::validation(format!("Invalid IUCN criterion '{}': {}", token, reason))
+    };
+
+    let mut chars = token.chars();
+    let letter = match chars.next() {
+        Some(c @ 'A'..='E') => c,
+        _ => return Err(invalid("must start with a criterion letter A-E")),
+    };
+    let rest = chars.as_str();
+    let (max_number, may_stand_alone) = Criterion::subcriterion_rules(letter);
+
+    if rest.is_empty() {
+        if may_stand_alone {
+            return Ok(Criterion { letter, subcriteria: Vec::new() });
+        }
+        return Err(invalid("missing numbered subcriterion"));
+    }
+    if max_number == 0 {
+        return Err(invalid("criterion takes no subcriteria"));
+    }
+
+    let mut subcriteria = Vec::new();
+    for part in rest.split('+') {
+        let sub = parse_subcriterion(part).ok_or_else(|| invalid(&format!("malformed subcriterion '{}'", part)))?;
+        if sub.number == 0 || sub.number > max_number {
+            return Err(invalid(&format!("subcriterion {} is out of range 1-{}", sub.number, max_number)));
+        }
+        let (allowed, required, need_details) = Criterion::qualifier_rules(letter, sub.number);
+        if let Some(q) = sub.qualifiers.iter().find(|q| !allowed.contains(q.letter)) {
+            return Err(invalid(&format!("{}{} does not take qualifier '{}'", letter, sub.number, q.letter)));
+        }
+        if sub.qualifiers.len() < required {
+            return Err(invalid(&format!("{}{} needs at least {} of qualifiers {}", letter, sub.number, required, allowed)));
+        }
+        if let Some(q) = sub.qualifiers.iter().find(|q| need_details.contains(q.letter) && q.details.is_empty()) {
+            return Err(invalid(&format!("qualifier '{}' of {}{} needs details, e.g. {}(ii)", q.letter, letter, sub.number, q.letter)));
+        }
+        subcriteria.push(sub);
+    }
+
+    Ok(Criterion { letter, subcriteria })
+}
+
+fn parse_subcriterion(part: &str) -> Option<Subcriterion> {
+    let digits_end = part.find(|c: char| !c.is_ascii_digit()).unwrap_or(part.len());
+    let number: u8 = part[..digits_end].parse().ok()?;
+
+    let mut qualifiers: Vec<Qualifier> = Vec::new();
+    let mut rest = &part[digits_end..];
+    while let Some(c) = rest.chars().next() {
+        if ('a'..='e').contains(&c) {
+            qualifiers.push(Qualifier { letter: c, details: Vec::new() });
+            rest = &rest[1..];
+        } else if c == '(' {
+            let close = rest.find(')')?;
+            let details: Vec<String> = rest[1..close].split(',').map(|d| d.trim().to_string()).collect();
+            if !details.iter().all(|d| is_roman_numeral(d)) {
+                return None;
+            }
+            // Details must follow a qualifier letter, and only once
+            let qualifier = qualifiers.last_mut().filter(|q| q.details.is_empty())?;
+            qualifier.details = details;
+            rest = &rest[close + 1..];
+        } else {
+            return None;
+        }
+    }
+
+    Some(Subcriterion { number, qualifiers })
+}
+
+fn is_roman_numeral(s: &str) -> bool {
+    matches!(s, "i" | "ii" | "iii" | "iv" | "v")
+}
@@ -1,9 +1,12 @@
+use std::fmt;
+use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use crate::error::DatabaseError;
 
 /// Growth stage enumeration
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GrowthStage {
     Seed,
     Germination,
@@ -54,7 +57,42 @@ impl Environment {
     }
 }
 
+impl Environment {
+    /// Checks that each recorded reading is physically plausible.
+    ///
+    /// Temperature must lie within -50..=70 °C, humidity within 0..=100 %, pH
+    /// within 0..=14, light within 0..=24 hours and CO2 must not be negative.
+    pub fn validate(&self) -> Result<(), DatabaseError> {
+        let ranges = [
+            ("temperature_celsius", self.temperature_celsius, -50.0, 70.0),
+            ("humidity_percent", self.humidity_percent, 0.0, 100.0),
+            ("ph_level", self.ph_level, 0.0, 14.0),
+            ("light_hours", self.light_hours, 0.0, 24.0),
+        ];
+        for (field, value, min, max) in ranges {
+            if let Some(value) = value {
+                if !value.is_finite() || value < min || value > max {
+                    return Err(DatabaseError::validation(format!(
+                        "{} {} is outside {}..={}", field, value, min, max
+                    )));
+                }
+            }
+        }
+        if let Some(co2) = self.co2_ppm.filter(|ppm| *ppm < 0) {
+            return Err(DatabaseError::validation(format!("co2_ppm {} must not be negative", co2)));
+        }
+        Ok(())
+    }
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CultivationRecord {
+    /// Creates a record stamped now, without validation; prefer `builder`.
     pub fn new(species_id: Uuid, growth_stage: GrowthStage, cultivator: String) -> Self {
         Self {
             id: Uuid::new_v4(),
@@ -67,4 +105,191 @@ impl CultivationRecord {
             cultivator,
         }
     }
+
+    /// Starts a validated record for a species at a growth stage.
+    pub fn builder(species_id: Uuid, growth_stage: GrowthStage) -> CultivationRecordBuilder {
+        CultivationRecordBuilder::new(species_id, growth_stage)
+    }
+}
+
+/// Chainable, validated construction of a `CultivationRecord`.
+#[derive(Debug, Clone)]
+pub struct CultivationRecordBuilder {
+    species_id: Uuid,
+    growth_stage: GrowthStage,
+    cultivator: String,
+    notes: Option<String>,
+    photos: Vec<String>,
+    environment: Option<Environment>,
+    recorded_at: Option<DateTime<Utc>>,
+}
+
+impl CultivationRecordBuilder {
+    /// Starts a record for a species at a growth stage.
+    pub fn new(species_id: Uuid, growth_stage: GrowthStage) -> Self {
+        Self {
+            species_id,
+            growth_stage,
+            cultivator: String::new(),
+            notes: None,
+            photos: Vec::new(),
+            environment: None,
+            recorded_at: None,
+        }
+    }
+
+    /// Sets who made the observation; required.
+    pub fn cultivator(mut self, cultivator: impl Into<String>) -> Self {
+        self.cultivator = cultivator.into();
+        self
+    }
+
+    /// Sets free-form notes.
+    pub fn notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = Some(notes.into());
+        self
+    }
+
+    /// Adds a photo reference.
+    pub fn photo(mut self, photo: impl Into<String>) -> Self {
+        self.photos.push(photo.into());
+        self
+    }
+
+    /// Links the environment readings taken with this record; validated on build.
+    pub fn environment(mut self, environment: &Environment) -> Self {
+        self.environment = Some(environment.clone());
+        self
+    }
+
+    /// Sets when the observation was made; defaults to the time of `build`.
+    pub fn recorded_at(mut self, recorded_at: DateTime<Utc>) -> Self {
+        self.recorded_at = Some(recorded_at);
+        self
+    }
+
+    /// Validates the fields and creates the record.
+    ///
+    /// Fails with a `ValidationError` if the cultivator is blank or any linked
+    /// environment reading is out of range.
+    pub fn build(self) -> Result<CultivationRecord, DatabaseError> {
+        let cultivator = self.cultivator.trim();
+        if cultivator.is_empty() {
+            return Err(DatabaseError::validation("Cultivator must not be empty"));
+        }
+        if let Some(environment) = &self.environment {
+            environment.validate()?;
+        }
+
+        Ok(CultivationRecord {
+            id: Uuid::new_v4(),
+            species_id: self.species_id,
+            growth_stage: self.growth_stage,
+            environment_id: self.environment.map(|e| e.id),
+            notes: self.notes.filter(|n| !n.trim().is_empty()),
+            photos: self.photos,
+            recorded_at: self.recorded_at.unwrap_or_else(Utc::now),
+            cultivator: cultivator.to_string(),
+        })
+    }
+}
+
+/// Kind of a discrete cultivation event.
+///
+/// Stored as text: `watering`, `feeding`, `repotting`, `pruning`,
+/// `pest_treatment`, `transplanting`, `harvesting` or `custom:<name>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum EventType {
+    Watering,
+    Feeding,
+    Repotting,
+    Pruning,
+    PestTreatment,
+    Transplanting,
+    Harvesting,
+
+    /// Any other action, e.g. "defoliation"
+    Custom(String),
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventType::Watering => write!(f, "watering"),
+            EventType::Feeding => write!(f, "feeding"),
+            EventType::Repotting => write!(f, "repotting"),
+            EventType::Pruning => write!(f, "pruning"),
+            EventType::PestTreatment => write!(f, "pest_treatment"),
+            EventType::Transplanting => write!(f, "transplanting"),
+            EventType::Harvesting => write!(f, "harvesting"),
+            EventType::Custom(name) => write!(f, "custom:{}", name),
+        }
+    }
+}
+
+impl FromStr for EventType {
+    type Err = DatabaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "watering" => Ok(EventType::Watering),
+            "feeding" => Ok(EventType::Feeding),
+            "repotting" => Ok(EventType::Repotting),
+            "pruning" => Ok(EventType::Pruning),
+            "pest_treatment" => Ok(EventType::PestTreatment),
+            "transplanting" => Ok(EventType::Transplanting),
+            "harvesting" => Ok(EventType::Harvesting),
+            _ => match s.strip_prefix("custom:") {
+                Some(name) if !name.trim().is_empty() => Ok(EventType::Custom(name.to_string())),
+                _ => Err(DatabaseError::validation(format!("Unknown event type '{}'", s))),
+            },
+        }
+    }
+}
+
+impl TryFrom<String> for EventType {
+    type Error = DatabaseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<EventType> for String {
+    fn from(event_type: EventType) -> Self {
+        event_type.to_string()
+    }
+}
+
+/// A discrete cultivation action, such as a watering or a pest treatment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CultivationEvent {
+    /// Unique identifier for the event
+    pub id: Uuid,
+
+    /// Reference to the species the action was taken on
+    pub species_id: Uuid,
+
+    /// What was done
+    pub event_type: EventType,
+
+    /// When it was done
+    pub timestamp: DateTime<Utc>,
+
+    /// Free-form detail, e.g. "500 ml, pH 6.2"
+    pub detail: Option<String>,
+}
+
+impl CultivationEvent {
+    /// Creates an event with a generated UUID.
+    pub fn new(species_id: Uuid, event_type: EventType, timestamp: DateTime<Utc>, detail: Option<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            species_id,
+            event_type,
+            timestamp,
+            detail,
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,242 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use crate::error::DatabaseError;
+
+/// A date known only to year, month or day precision, as on many herbarium labels.
+///
+/// Formats as the ISO 8601 form used by Darwin Core `eventDate`: `1987`,
+/// `1987-03` or `1987-03-14`, with years outside 0000-9999 in the signed
+/// expanded form (`+10000`, `-0005`). Range checks treat a partial date as
+/// the whole interval it implies, so `1987-03` covers 1 to 31 March 1987.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum PartialDate {
+    /// Only the year is known
+    Year(i32),
+
+    /// Year and month (1-12) are known
+    YearMonth(i32, u32),
+
+    /// The exact day is known
+    Full(NaiveDate),
+}
+
+impl PartialDate {
+    /// Creates a year-and-month date, rejecting months outside 1-12.
+    pub fn year_month(year: i32, month: u32) -> Result<Self, DatabaseError> {
+        if !(1..=12).contains(&month) {
+            return Err(DatabaseError::validation(format!("Invalid month {}-{:02}", year, month)));
+        }
+        Ok(PartialDate::YearMonth(year, month))
+    }
+
+    /// First and last day of the implied interval, both inclusive.
+    ///
+    /// Returns `None` for a `YearMonth` built directly with an invalid month
+    /// or a year outside chrono's range.
+    pub fn interval(&self) -> Option<(NaiveDate, NaiveDate)> {
+        match *self {
+            PartialDate::Year(year) => Some((NaiveDate::from_ymd_opt(year, 1, 1)?, NaiveDate::from_ymd_opt(year, 12, 31)?)),
+            PartialDate::YearMonth(year, month) => {
+                let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+                let next_month = if month == 12 {
+                    NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+                } else {
+                    NaiveDate::from_ymd_opt(year, month + 1, 1)?
+                };
+                Some((start, next_month.pred_opt()?))
+            }
+            PartialDate::Full(date) => Some((date, date)),
+        }
+    }
+
+    /// Whether the implied interval overlaps `from..=to`.
+    pub fn overlaps(&self, from: NaiveDate, to: NaiveDate) -> bool {
+        match self.interval() {
+            Some((start, end)) => start <= to && end >= from,
+            None => false,
+        }
+    }
+
+    /// The year, known at every precision
+    pub fn year(&self) -> i32 {
+        match *self {
+            PartialDate::Year(year) | PartialDate::YearMonth(year, _) => year,
+            PartialDate::Full(date) => date.year(),
+        }
+    }
+}
+
+impl fmt::Display for PartialDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartialDate::Year(year) => write!(f, "{}", IsoYear(*year)),
+            PartialDate::YearMonth(year, month) => write!(f, "{}-{:02}", IsoYear(*year), month),
+            PartialDate::Full(date) => write!(f, "{}-{:02}-{:02}", IsoYear(date.year()), date.month(), date.day()),
+        }
+    }
+}
+
+/// A year in ISO 8601 form: four digits for 0000-9999, otherwise the
+/// expanded form with an explicit sign, e.g. `+10000` or `-0005`
+struct IsoYear(i32);
+
+impl fmt::Display for IsoYear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            0..=9999 => write!(f, "{:04}", self.0),
+            year if year < 0 => write!(f, "-{:04}", year.unsigned_abs()),
+            year => write!(f, "+{}", year),
+        }
+    }
+}
+
+impl FromStr for PartialDate {
+    type Err = DatabaseError;
+
+    /// Parses the ISO 8601 forms produced by `Display`, including signed
+    /// expanded years.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || DatabaseError::validation(format!("Invalid partial date '{}'", s));
+        let (negative, unsigned) = match s.strip_prefix('-') {
+            Some(rest) => (true, Some(rest)),
+            None => (false, s.strip_prefix('+')),
+        };
+        let parts: Vec<&str> = unsigned.unwrap_or(s).split('-').collect();
+        if parts.iter().any(|p| p.is_empty() || !p.bytes().all(|b| b.is_ascii_digit())) {
+            return Err(invalid());
+        }
+
+        // Unsigned years are exactly four digits; signed ones at least four
+        let year_digits = parts[0];
+        if year_digits.len() < 4 || (unsigned.is_none() && year_digits.len() != 4) {
+            return Err(invalid());
+        }
+        let year: i64 = year_digits.parse().map_err(|_| invalid())?;
+        let year = i32::try_from(if negative { -year } else { year }).map_err(|_| invalid())?;
+        let two_digits = |part: &str| -> Result<u32, DatabaseError> {
+            if part.len() != 2 {
+                return Err(invalid());
+            }
+            part.parse().map_err(|_| invalid())
+        };
+
+        match parts[1..] {
+            [] => Ok(PartialDate::Year(year)),
+            [month] => PartialDate::year_month(year, two_digits(month)?).map_err(|_| invalid()),
+            [month, day] => NaiveDate::from_ymd_opt(year, two_digits(month)?, two_digits(day)?)
+                .map(PartialDate::Full)
+                .ok_or_else(invalid),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl TryFrom<String> for PartialDate {
+    type Error = DatabaseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<PartialDate> for String {
+    fn from(date: PartialDate) -> Self {
+        date.to_string()
+    }
+}
+
+/// Which field comes first in an all-numeric date such as `03/07/2019`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateOrder {
+    /// `DD/MM/YYYY`, as in most of Europe
+    #[default]
+    DayFirst,
+
+    /// `MM/DD/YYYY`, as in the United States
+    MonthFirst,
+}
+
+/// Formats tried for dates written with month names, most specific first
+const NAMED_MONTH_FORMATS: &[&str] = &["%B %d, %Y", "%B %d %Y", "%d %B %Y", "%d %B, %Y"];
+
+/// Parses a date as written by import sources, assuming day-first numeric dates.
+///
+/// See `parse_flexible_date_with`.
+pub fn parse_flexible_date(s: &str) -> Result<PartialDate, DatabaseError> {
+    parse_flexible_date_with(s, DateOrder::default())
+}
+
+/// Parses a date as written by import sources.
+///
+/// Tries, in order: the ISO forms accepted by `PartialDate::from_str`
+/// (`2019-07-18`, `2019-07`, `2019`); numeric dates separated by `/` or `.`;
+/// month names (`July 18, 2019`, `18 Jul 2019`, `July 2019`); and finally a
+/// lone four-digit year anywhere in the string (`ca. 1890`), giving year
+/// precision. `order` settles numeric dates that are valid both ways, such as
+/// `03/07/2019`; one that is valid only the other way, like `18/07/2019` read
+/// month-first, is still accepted. Returns a `ValidationError` naming the value
+/// when nothing matches.
+pub fn parse_flexible_date_with(s: &str, order: DateOrder) -> Result<PartialDate, DatabaseError> {
+    let s = s.trim();
+    if let Ok(date) = s.parse::<PartialDate>() {
+        return Ok(date);
+    }
+    if let Some(date) = parse_numeric_date(s, order) {
+        return Ok(PartialDate::Full(date));
+    }
+    if let Some(date) = parse_named_month_date(s) {
+        return Ok(date);
+    }
+
+    let mut years = s
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|token| token.len() == 4);
+    match (years.next(), years.next()) {
+        (Some(year), None) => Ok(PartialDate::Year(year.parse().map_err(|_| unparseable_date(s))?)),
+        _ => Err(unparseable_date(s)),
+    }
+}
+
+fn unparseable_date(s: &str) -> DatabaseError {
+    DatabaseError::validation(format!("Unrecognised date '{}'", s))
+}
+
+fn parse_numeric_date(s: &str, order: DateOrder) -> Option<NaiveDate> {
+    let separator = ['/', '.'].into_iter().find(|sep| s.contains(*sep))?;
+    let parts: Vec<&str> = s.split(separator).map(str::trim).collect();
+    let [first, second, year] = parts.as_slice() else {
+        return None;
+    };
+    if year.len() != 4 {
+        return None;
+    }
+    let first: u32 = first.parse().ok()?;
+    let second: u32 = second.parse().ok()?;
+    let year: i32 = year.parse().ok()?;
+
+    let day_first = NaiveDate::from_ymd_opt(year, second, first);
+    let month_first = NaiveDate::from_ymd_opt(year, first, second);
+    match order {
+        DateOrder::DayFirst => day_first.or(month_first),
+        DateOrder::MonthFirst => month_first.or(day_first),
+    }
+}
+
+fn parse_named_month_date(s: &str) -> Option<PartialDate> {
+    let tokens: Vec<&str> = s.split(|c: char| c.is_whitespace() || c == ',').filter(|t| !t.is_empty()).collect();
+    match tokens.as_slice() {
+        // Month and year only: parse against a fixed first day
+        [month, year] if year.len() == 4 => NaiveDate::parse_from_str(&format!("1 {} {}", month, year), "%d %B %Y")
+            .ok()
+            .map(|date| PartialDate::YearMonth(date.year(), date.month())),
+        [_, _, year] if year.len() == 4 => NAMED_MONTH_FORMATS
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(s, format).ok())
+            .map(PartialDate::Full),
+        _ => None,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use crate::error::DatabaseError;
+
+/// Mean Earth radius in kilometres, as used by the haversine formula
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// A validated WGS84 coordinate pair in decimal degrees.
+///
+/// Construction and deserialization both go through `new`, so every value
+/// holds in-range coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedGeoPoint")]
+pub struct GeoPoint {
+    /// Latitude in decimal degrees, between -90 and 90
+    latitude: f64,
+
+    /// Longitude in decimal degrees, between -180 and 180
+    longitude: f64,
+}
+
+/// Serialized form of a GeoPoint, validated on conversion
+#[derive(Deserialize)]
+struct UncheckedGeoPoint {
+    latitude: f64,
+    longitude: f64,
+}
+
+impl TryFrom<UncheckedGeoPoint> for GeoPoint {
+    type Error = DatabaseError;
+
+    fn try_from(point: UncheckedGeoPoint) -> Result<Self, Self::Error> {
+        Self::new(point.latitude, point.longitude)
+    }
+}
+
+impl GeoPoint {
+    /// Creates a new GeoPoint, rejecting out-of-range or non-finite coordinates.
+    pub fn new(latitude: f64, longitude: f64) -> Result<Self, DatabaseError> {
+        if !latitude.is_finite() || !(-90.0..=90.0).contains(&latitude) {
+            return Err(DatabaseError::validation(format!("Latitude {} is outside -90..90", latitude)));
+        }
+        if !longitude.is_finite() || !(-180.0..=180.0).contains(&longitude) {
+            return Err(DatabaseError::validation(format!("Longitude {} is outside -180..180", longitude)));
+        }
+
+        Ok(Self { latitude, longitude })
+    }
+
+    /// Latitude in decimal degrees, between -90 and 90.
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    /// Longitude in decimal degrees, between -180 and 180.
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// Creates a GeoPoint from optional storage columns.
+    ///
+    /// Returns `Ok(None)` unless both coordinates are present.
+    pub fn from_columns(latitude: Option<f64>, longitude: Option<f64>) -> Result<Option<Self>, DatabaseError> {
+        match (latitude, longitude) {
+            (Some(lat), Some(lon)) => Self::new(lat, lon).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Great-circle distance to another point in kilometres (haversine formula).
+    pub fn distance_km_to(&self, other: &GeoPoint) -> f64 {
+        let lat1 = self.latitude.to_radians();
+        let lat2 = other.latitude.to_radians();
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+}
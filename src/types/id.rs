@@ -0,0 +1,52 @@
+use uuid::Uuid;
+
+/// Namespace for identifiers derived by `DeterministicIdStrategy`
+pub const BOTANICA_ID_NAMESPACE: Uuid = Uuid::from_u128(0x6f1c_2b7e_4d8a_5e39_9c41_0a7d_3b25_e816);
+
+/// Source of IDs for records that the database creates on the caller's behalf
+///
+/// Records built by the caller keep whatever ID they carry; a strategy only
+/// matters where the crate mints one itself, as in `get_or_create_family_with_ids`.
+pub trait IdStrategy: Send + Sync {
+    /// Produce the ID for the next new record
+    fn next_id(&self) -> Uuid;
+}
+
+/// Random version 4 IDs; the default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomIdStrategy;
+
+impl IdStrategy for RandomIdStrategy {
+    fn next_id(&self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+/// Version 5 IDs derived from an external key, stable across re-imports
+///
+/// The same namespace and key always give the same ID, so a record imported
+/// from another system keeps its identity when imported again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeterministicIdStrategy {
+    namespace: Uuid,
+    key: String,
+}
+
+impl DeterministicIdStrategy {
+    /// Derive IDs from a key in the `BOTANICA_ID_NAMESPACE` namespace
+    pub fn new(key: impl Into<String>) -> Self {
+        Self::with_namespace(BOTANICA_ID_NAMESPACE, key)
+    }
+
+    /// Derive IDs from a key in a caller-chosen namespace, e.g. one per source system
+    pub fn with_namespace(namespace: Uuid, key: impl Into<String>) -> Self {
+        Self { namespace, key: key.into() }
+    }
+}
+
+impl IdStrategy for DeterministicIdStrategy {
+    /// The RFC 4122 version 5 UUID of the key within the namespace
+    fn next_id(&self) -> Uuid {
+        Uuid::new_v5(&self.namespace, self.key.as_bytes())
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An image, video or sound recording attached to a species.
+///
+/// Fields follow the Audubon Core multimedia terms used by the Darwin Core
+/// multimedia extension.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Media {
+    /// Unique identifier for the media item
+    pub id: Uuid,
+    
+    /// Reference to the species shown
+    pub species_id: Uuid,
+    
+    /// Where the media file can be retrieved
+    pub url: String,
+    
+    /// Media type, e.g. `StillImage`, `MovingImage` or `Sound`
+    pub media_type: String,
+    
+    /// License the media is published under, e.g. `CC-BY-4.0`; required for attribution
+    pub license: String,
+    
+    /// The person or organisation that created the media
+    pub creator: Option<String>,
+    
+    /// A short caption
+    pub title: Option<String>,
+}
+
+impl Media {
+    /// Creates a new Media instance with a generated UUID.
+    pub fn new(
+        species_id: Uuid,
+        url: String,
+        media_type: String,
+        license: String,
+        creator: Option<String>,
+        title: Option<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            species_id,
+            url,
+            media_type,
+            license,
+            creator,
+            title,
+        }
+    }
+}
@@ -2,8 +2,28 @@ pub mod species;
 pub mod genus;
 pub mod family;
 pub mod cultivation;
+pub mod care;
+pub mod conservation;
+pub mod geo;
+pub mod media;
+pub mod date;
+pub mod accession;
+pub mod source;
+pub mod annotation;
+pub mod id;
+pub mod collection;
 
-pub use species::Species;
+pub use species::{Species, FieldChange};
 pub use genus::Genus;
 pub use family::Family;
-pub use cultivation::{GrowthStage, Environment, CultivationRecord};
\ No newline at end of file
+pub use cultivation::{GrowthStage, Environment, CultivationRecord, CultivationRecordBuilder, CultivationEvent, EventType};
+pub use care::{CareAction, CareSchedule, care_schedule};
+pub use geo::GeoPoint;
+pub use media::Media;
+pub use date::{PartialDate, DateOrder, parse_flexible_date, parse_flexible_date_with};
+pub use accession::AccessionNumber;
+pub use source::DataSource;
+pub use annotation::Annotation;
+pub use collection::Collection;
+pub use id::{IdStrategy, RandomIdStrategy, DeterministicIdStrategy};
+pub use conservation::{IUCNCategory, Criterion, Subcriterion, Qualifier, parse_iucn_criteria};
\ No newline at end of file
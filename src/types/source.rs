@@ -0,0 +1,65 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use crate::error::DatabaseError;
+
+/// Where a record came from.
+///
+/// Stored as text: `manual`, `gbif`, `inaturalist` or `import:<name>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum DataSource {
+    /// Entered by hand; the default for records with no recorded source
+    #[default]
+    Manual,
+
+    /// Imported from the Global Biodiversity Information Facility
+    Gbif,
+
+    /// Imported from iNaturalist
+    INaturalist,
+
+    /// Imported from another named source, e.g. a partner's spreadsheet
+    Import(String),
+}
+
+impl fmt::Display for DataSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataSource::Manual => write!(f, "manual"),
+            DataSource::Gbif => write!(f, "gbif"),
+            DataSource::INaturalist => write!(f, "inaturalist"),
+            DataSource::Import(name) => write!(f, "import:{}", name),
+        }
+    }
+}
+
+impl FromStr for DataSource {
+    type Err = DatabaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "manual" => Ok(DataSource::Manual),
+            "gbif" => Ok(DataSource::Gbif),
+            "inaturalist" => Ok(DataSource::INaturalist),
+            _ => match s.strip_prefix("import:") {
+                Some(name) if !name.is_empty() => Ok(DataSource::Import(name.to_string())),
+                _ => Err(DatabaseError::validation(format!("Unknown data source '{}'", s))),
+            },
+        }
+    }
+}
+
+impl TryFrom<String> for DataSource {
+    type Error = DatabaseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<DataSource> for String {
+    fn from(source: DataSource) -> Self {
+        source.to_string()
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use super::source::DataSource;
 
 /// Represents a species in the botanical taxonomy system.
 /// 
@@ -25,6 +26,27 @@ pub struct Species {
     
     /// Conservation status according to IUCN or other conservation organizations
     pub conservation_status: Option<String>,
+    
+    /// Where this record came from
+    #[serde(default)]
+    pub source: DataSource,
+    
+    /// The record's identifier in its source, e.g. a GBIF taxon key
+    #[serde(default)]
+    pub source_record_id: Option<String>,
+}
+
+/// A single field that differs between two species records.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldChange {
+    /// Name of the changed field, e.g. `specific_epithet`
+    pub field: &'static str,
+    
+    /// Value in the existing record, `None` when unset
+    pub old_value: Option<String>,
+    
+    /// Value in the proposed record, `None` when unset
+    pub new_value: Option<String>,
 }
 
 impl Species {
@@ -43,6 +65,8 @@ impl Species {
             authority,
             publication_year,
             conservation_status,
+            source: DataSource::Manual,
+            source_record_id: None,
         }
     }
 
@@ -62,9 +86,18 @@ impl Species {
             authority,
             publication_year,
             conservation_status,
+            source: DataSource::Manual,
+            source_record_id: None,
         }
     }
 
+    /// Returns the species with its provenance set.
+    pub fn with_source(mut self, source: DataSource, source_record_id: Option<String>) -> Self {
+        self.source = source;
+        self.source_record_id = source_record_id;
+        self
+    }
+
     /// Returns the specific epithet.
     pub fn get_specific_epithet(&self) -> &str {
         &self.specific_epithet
@@ -94,4 +127,24 @@ impl Species {
     pub fn has_conservation_status(&self) -> bool {
         self.conservation_status.is_some()
     }
+
+    /// Lists the nomenclatural fields that differ from `other`, treating `self` as
+    /// the existing record and `other` as the proposed one.
+    ///
+    /// Compares the genus, specific epithet, authority and publication year, in
+    /// that order. Identical records yield an empty list.
+    pub fn diff(&self, other: &Species) -> Vec<FieldChange> {
+        let fields = [
+            ("genus_id", Some(self.genus_id.to_string()), Some(other.genus_id.to_string())),
+            ("specific_epithet", Some(self.specific_epithet.clone()), Some(other.specific_epithet.clone())),
+            ("authority", Some(self.authority.clone()), Some(other.authority.clone())),
+            ("publication_year", self.publication_year.map(|y| y.to_string()), other.publication_year.map(|y| y.to_string())),
+        ];
+        
+        fields
+            .into_iter()
+            .filter(|(_, old_value, new_value)| old_value != new_value)
+            .map(|(field, old_value, new_value)| FieldChange { field, old_value, new_value })
+            .collect()
+    }
 }
\ No newline at end of file